@@ -0,0 +1,73 @@
+use crate::duel::Duel;
+use crate::nerds::ActionType;
+use crate::solver::Analysis;
+
+// Chance (out of 100) of going for the best action found by searching ahead instead of a random action
+const AGGRESSION: i32 = 50;
+const SEARCH_DEPTH: usize = 2;
+
+// How far off wrong answers can be
+const MISTAKES: [i32; 4] = [-10, -1, 1, 10];
+
+// A computer controlled nerd
+#[derive(Copy, Clone)]
+pub struct Ai {
+    accuracy: i32,
+}
+
+impl Ai {
+    // Creates a new AI that answers right the given percent of the time
+    pub const fn new(accuracy: i32) -> Self {
+        Self { accuracy }
+    }
+
+    // Returns the percent of the time the AI answers right
+    pub fn accuracy(&self) -> i32 {
+        self.accuracy
+    }
+
+    // Picks an action for the current nerd of the duel
+    pub fn choose_action(&self, duel: &Duel) -> usize {
+        let nerd = &duel.nerds[duel.current_nerd];
+        let other = &duel.nerds[duel.other_nerd()];
+        let damages = nerd
+            .actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| action.action_type() == ActionType::Damage);
+
+        for (i, _) in damages.clone() {
+            if nerd
+                .equation(i, other, false)
+                .is_ok_and(|equation| equation.answer() < 1)
+            {
+                return i;
+            }
+        }
+        if fastrand::i32(0..100) < AGGRESSION {
+            return Analysis::new(&duel.nerds, duel.current_nerd, SEARCH_DEPTH).action;
+        }
+        fastrand::usize(0..nerd.actions.len())
+    }
+
+    // Returns the answer the AI gives to the duel's equation (a wrong choice if answers are multiple choice)
+    pub fn answer(&self, duel: &Duel) -> i32 {
+        let wrong: Vec<i32> = duel
+            .choices()
+            .into_iter()
+            .filter(|&choice| choice != duel.answer())
+            .collect();
+        if fastrand::i32(0..100) < self.accuracy {
+            duel.answer()
+        } else if !wrong.is_empty() {
+            wrong[fastrand::usize(0..wrong.len())]
+        } else {
+            // A mistake goes the other way if it would go past the biggest (or smallest) number
+            let mistake = MISTAKES[fastrand::usize(0..MISTAKES.len())];
+            match duel.answer().saturating_add(mistake) {
+                answer if answer == duel.answer() => answer.saturating_sub(mistake),
+                answer => answer,
+            }
+        }
+    }
+}
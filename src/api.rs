@@ -0,0 +1,393 @@
+use crate::duel::Duel;
+use crate::game::{self, GAME_END_MESSAGE};
+use crate::http::{Request, Response};
+use crate::json::Json;
+use crate::nerds::{Nerd, Nerds, NERDS};
+use crate::options::Options;
+use crate::unlocks::{Unlockable, Unlocks};
+use std::collections::HashMap;
+use std::io;
+use std::net::TcpListener;
+use std::time::Duration;
+
+// Port the API listens on when one isn't given
+pub const DEFAULT_PORT: u16 = 7878;
+
+// Longest a client can take to send its request (requests are answered one at a time, so a client
+// that sends nothing would otherwise stop every other client from being answered)
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Local HTTP server that plays matches with the game's rules and answers in JSON
+pub struct Api {
+    options: Options,
+    matches: HashMap<usize, ApiMatch>,
+    next_id: usize,
+}
+
+impl Api {
+    // Creates an API with no matches that uses the saved options
+    pub fn new() -> Self {
+        Self {
+            options: Options::load(),
+            matches: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    // Answers requests on localhost forever (one at a time)
+    pub fn serve(&mut self, port: u16) -> io::Result<()> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        println!("Listening on http://127.0.0.1:{port}");
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+            let response = match Request::read(&stream) {
+                Ok(request) => self.handle(&request),
+                Err(_) => Response::error(400, "The request couldn't be read"),
+            };
+            let _ = response.write(&stream);
+        }
+        Ok(())
+    }
+
+    // Returns the response to a request
+    pub fn handle(&mut self, request: &Request) -> Response {
+        let Some(body) = request.json() else {
+            return Response::error(400, "The body isn't valid JSON");
+        };
+        let segments = request.segments();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["nerds"]) => Response::ok(roster()),
+            ("POST", ["matches"]) => self.create_match(&body),
+            (method, ["matches", id, rest @ ..]) => {
+                let Some((id, api_match)) = id
+                    .parse()
+                    .ok()
+                    .and_then(|id| Some((id, self.matches.get_mut(&id)?)))
+                else {
+                    return Response::error(404, "There is no match with that id");
+                };
+                let result = match (method, rest) {
+                    ("GET", []) => Ok(()),
+                    ("GET", ["log"]) => {
+                        let mut log = Json::object([("id", id.into())]);
+                        log.extend(api_match.log());
+                        return Response::ok(log);
+                    }
+                    ("POST", ["action"]) => api_match.choose_action(&body),
+                    ("POST", ["answer"]) => api_match.submit(&body),
+                    ("POST", ["taunt"]) => {
+                        let nerd = body
+                            .get("nerd")
+                            .and_then(Json::as_number)
+                            .filter(|nerd| (0..2).contains(nerd));
+                        match nerd {
+                            Some(nerd) => api_match.taunt(nerd as usize, &body),
+                            None => Err(Response::error(400, "\"nerd\" has to be 0 or 1")),
+                        }
+                    }
+                    (_, [] | ["log"] | ["action"] | ["answer"] | ["taunt"]) => {
+                        return Response::error(405, "That method isn't allowed here")
+                    }
+                    _ => return Response::error(404, "There is nothing here"),
+                };
+                match result {
+                    Ok(()) => Response::ok(self.state(id)),
+                    Err(response) => response,
+                }
+            }
+            (_, ["nerds"] | ["matches"]) => Response::error(405, "That method isn't allowed here"),
+            _ => Response::error(404, "There is nothing here"),
+        }
+    }
+
+    // Starts a match between two unlocked nerds given by name
+    fn create_match(&mut self, body: &Json) -> Response {
+        let names: Vec<&str> = body
+            .get("nerds")
+            .and_then(Json::as_array)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(Json::as_str)
+            .collect();
+        let [first, second] = names.as_slice() else {
+            return Response::error(400, "\"nerds\" has to be the names of two nerds");
+        };
+        let nerds = match (find_nerd(first), find_nerd(second)) {
+            (Ok(first), Ok(second)) => [first, second],
+            (Err(response), _) | (_, Err(response)) => return response,
+        };
+        let api_match = match ApiMatch::new(nerds, &self.options) {
+            Ok(api_match) => api_match,
+            Err(response) => return response,
+        };
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.matches.insert(id, api_match);
+        Response {
+            status: 201,
+            body: self.state(id),
+        }
+    }
+
+    // Returns the state of a match with its id
+    fn state(&self, id: usize) -> Json {
+        let mut state = Json::object([("id", id.into())]);
+        state.extend(self.matches[&id].state(self.options.reveal_critical));
+        state
+    }
+}
+
+// A match played through JSON requests
+pub struct ApiMatch {
+    duel: Duel,
+    answering: bool,
+    last_answer_right: Option<bool>,
+    forfeited: Option<usize>,
+    locked_actions: Vec<&'static str>,
+}
+
+impl ApiMatch {
+    // Starts a match between two nerds with the rules in the options
+    pub fn new(nerds: Nerds, options: &Options) -> Result<Self, Response> {
+        if nerds[0].name == nerds[1].name && !options.mirror_matches {
+            return Err(Response::error(
+                400,
+                "Mirror matches are turned off in the options",
+            ));
+        }
+        Ok(Self {
+            duel: Duel::start(nerds, options, None),
+            answering: false,
+            last_answer_right: None,
+            forfeited: None,
+            locked_actions: Unlocks::load().locked_actions(),
+        })
+    }
+
+    // Returns the index of the nerd whose turn it is
+    pub fn current_nerd(&self) -> usize {
+        self.duel.current_nerd
+    }
+
+    // Returns the index of the winning nerd (by knockout or forfeit), otherwise none
+    pub fn winner(&self) -> Option<usize> {
+        self.forfeited
+            .map(|nerd| 1 - nerd)
+            .or_else(|| self.duel.winner())
+    }
+
+    // Chooses the current nerd's action (given as "action") and creates its equation
+    pub fn choose_action(&mut self, body: &Json) -> Result<(), Response> {
+        if self.winner().is_some() {
+            return Err(Response::error(409, "The match is over"));
+        } else if self.answering {
+            return Err(Response::error(
+                409,
+                "An action was already chosen this turn",
+            ));
+        }
+        let actions = self.duel.nerds[self.duel.current_nerd].actions.len();
+        let Some(action) = body
+            .get("action")
+            .and_then(Json::as_number)
+            .and_then(|action| usize::try_from(action).ok())
+            .filter(|&action| action < actions)
+        else {
+            return Err(Response::error(
+                400,
+                &format!("\"action\" has to be from 0 to {}", actions - 1),
+            ));
+        };
+        let name = self.duel.nerds[self.duel.current_nerd].actions[action].base_name();
+        if self.locked_actions.contains(&name) {
+            return Err(Response::error(403, "That action is still locked"));
+        }
+        self.answering = self.duel.choose_action(action);
+        Ok(())
+    }
+
+    // Submits an answer (given as "answer") to the equation of the chosen action
+    pub fn submit(&mut self, body: &Json) -> Result<(), Response> {
+        if self.winner().is_some() {
+            return Err(Response::error(409, "The match is over"));
+        } else if !self.answering {
+            return Err(Response::error(409, "An action has to be chosen first"));
+        }
+        let Some(answer) = body
+            .get("answer")
+            .and_then(Json::as_number)
+            .and_then(|answer| i32::try_from(answer).ok())
+        else {
+            return Err(Response::error(400, "\"answer\" has to be a whole number"));
+        };
+        self.last_answer_right = Some(self.duel.submit(answer));
+        self.answering = false;
+        if let Some(winner) = self.duel.winner() {
+            let names = [
+                self.duel.nerds[winner].name,
+                self.duel.nerds[1 - winner].name,
+            ];
+            self.duel
+                .add_message(&game::fill_names(GAME_END_MESSAGE, names));
+        }
+        Ok(())
+    }
+
+    // Has a nerd say one of their taunts (given as "taunt"), which they can do once a turn
+    pub fn taunt(&mut self, nerd: usize, body: &Json) -> Result<(), Response> {
+        if !self.duel.can_taunt(nerd) || self.winner().is_some() {
+            return Err(Response::error(
+                409,
+                "That nerd can't taunt again until the next turn",
+            ));
+        }
+        let taunts = self.duel.nerds[nerd].taunts.len();
+        let taunt = body
+            .get("taunt")
+            .and_then(Json::as_number)
+            .and_then(|taunt| usize::try_from(taunt).ok())
+            .filter(|&taunt| taunt < taunts);
+        match taunt {
+            Some(taunt) => {
+                self.duel.taunt(nerd, taunt);
+                Ok(())
+            }
+            None => Err(Response::error(
+                400,
+                &format!("\"taunt\" has to be from 0 to {}", taunts - 1),
+            )),
+        }
+    }
+
+    // Ends the match with a nerd forfeiting (if it isn't already over)
+    pub fn forfeit(&mut self, nerd: usize) {
+        if self.winner().is_none() {
+            self.forfeited = Some(nerd);
+            self.answering = false;
+            let (name, other) = (self.duel.nerds[nerd].name, self.duel.nerds[1 - nerd].name);
+            self.duel.add_message(&format!(
+                "{name} left the fight, so {other} wins by forfeit"
+            ));
+        }
+    }
+
+    // Returns the state of the match (the answer isn't included)
+    pub fn state(&self, reveal_critical: bool) -> Json {
+        let duel = &self.duel;
+        let phase = if self.winner().is_some() {
+            "finished"
+        } else if self.answering {
+            "answering"
+        } else {
+            "choosing"
+        };
+        let equation = self.answering.then(|| Json::string(duel.equation()));
+        let choices = (self.answering && duel.multiple_choice)
+            .then(|| Json::Array(duel.choices().into_iter().map(Json::from).collect()));
+        let critical = reveal_critical.then(|| duel.critical());
+        Json::object([
+            (
+                "nerds",
+                Json::Array(
+                    duel.nerds
+                        .iter()
+                        .map(|nerd| nerd_json(nerd, &self.locked_actions))
+                        .collect(),
+                ),
+            ),
+            ("current_nerd", duel.current_nerd.into()),
+            ("phase", Json::string(phase)),
+            ("equation", equation.unwrap_or(Json::Null)),
+            ("choices", choices.unwrap_or(Json::Null)),
+            ("missing_number", (self.answering && duel.missing()).into()),
+            ("critical", critical.into()),
+            ("last_answer_right", self.last_answer_right.into()),
+            (
+                "taunts",
+                Json::Array(
+                    (0..2)
+                        .map(|nerd| duel.taunt_said(nerd).map(Json::string).into())
+                        .collect(),
+                ),
+            ),
+            ("winner", self.winner().into()),
+        ])
+    }
+
+    // Returns every message about what has happened in the match
+    pub fn log(&self) -> Json {
+        let messages = self
+            .duel
+            .messages()
+            .iter()
+            .map(|message| Json::string(message))
+            .collect();
+        Json::object([("messages", Json::Array(messages))])
+    }
+}
+
+// Returns every unlocked roster nerd
+pub fn roster() -> Json {
+    let unlocks = Unlocks::load();
+    let locked_actions = unlocks.locked_actions();
+    let nerds = NERDS
+        .iter()
+        .filter(|nerd| unlocks.is_unlocked(Unlockable::Nerd(nerd.name)))
+        .map(|nerd| nerd_json(nerd, &locked_actions))
+        .collect();
+    Json::object([("nerds", Json::Array(nerds))])
+}
+
+// Returns the unlocked roster nerd with a name (ignoring case)
+pub fn find_nerd(name: &str) -> Result<Nerd, Response> {
+    let unlocks = Unlocks::load();
+    NERDS
+        .into_iter()
+        .find(|nerd| nerd.name.eq_ignore_ascii_case(name))
+        .filter(|nerd| unlocks.is_unlocked(Unlockable::Nerd(nerd.name)))
+        .ok_or_else(|| Response::error(400, &format!("There is no nerd named {name}")))
+}
+
+// Returns a nerd's stats and actions (and which of them players can't use yet)
+fn nerd_json(nerd: &Nerd, locked_actions: &[&str]) -> Json {
+    let actions = nerd
+        .actions
+        .iter()
+        .map(|action| {
+            Json::object([
+                ("name", Json::string(action.base_name())),
+                ("type", Json::string(action.action_type().name())),
+                ("value", action.value().into()),
+                ("subject", Json::string(action.subject().name())),
+                (
+                    "locked",
+                    locked_actions.contains(&action.base_name()).into(),
+                ),
+            ])
+        })
+        .collect();
+    Json::object([
+        ("name", Json::string(nerd.name)),
+        ("subject", Json::string(nerd.subject.name())),
+        ("health", nerd.health.into()),
+        ("max_health", nerd.max_health.into()),
+        ("speed", nerd.speed.into()),
+        ("multiplier", nerd.multiplier.into()),
+        ("critical_chance", nerd.critical_chance.into()),
+        ("critical_multiplier", nerd.critical_multiplier.into()),
+        ("actions", Json::Array(actions)),
+        (
+            "taunts",
+            Json::Array(
+                nerd.taunts
+                    .iter()
+                    .map(|taunt| Json::string(taunt))
+                    .collect(),
+            ),
+        ),
+    ])
+}
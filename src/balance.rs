@@ -0,0 +1,129 @@
+use crate::creator::{MAX_NAME_LENGTH, MAX_SPRITE_LINES, MAX_SPRITE_WIDTH};
+use crate::nerds::{ActionType, Nerd};
+use crate::script::Script;
+
+// Power that a nerd's health and actions are allowed to add up to (the nerd creator's budget is the top)
+pub const MIN_POWER: i32 = 16;
+pub const MAX_POWER: i32 = 22;
+
+// How much health is worth one point of power
+const HEALTH_PER_POWER: i32 = 50;
+
+// What was found out about a nerd
+pub struct Report {
+    pub name: &'static str,
+    pub power: i32,
+    pub problems: Vec<String>,
+}
+
+// Returns a nerd's power, which is how much its health and actions are worth
+pub fn power(nerd: &Nerd) -> i32 {
+    let actions: i32 = nerd
+        .actions
+        .iter()
+        .map(|action| action.value() * action_weight(action.action_type()))
+        .sum();
+    nerd.max_health / HEALTH_PER_POWER + actions
+}
+
+// Returns how much power each value of an action type is worth
+fn action_weight(action_type: ActionType) -> i32 {
+    match action_type {
+        ActionType::Damage | ActionType::Heal => 2,
+        ActionType::Weaken | ActionType::Strengthen => 1,
+    }
+}
+
+// Checks every nerd of a roster and returns what was found (power has to be inside of a band)
+pub fn validate(roster: &[Nerd], min_power: i32, max_power: i32) -> Vec<Report> {
+    roster
+        .iter()
+        .map(|nerd| {
+            let power = power(nerd);
+            let mut problems = Vec::new();
+            if power < min_power {
+                problems.push(format!(
+                    "Its power is below the allowed band of {min_power} to {max_power}"
+                ));
+            } else if power > max_power {
+                problems.push(format!(
+                    "Its power is above the allowed band of {min_power} to {max_power}"
+                ));
+            }
+            problems.extend(nerd_problems(nerd, roster));
+            Report {
+                name: nerd.name,
+                power,
+                problems,
+            }
+        })
+        .collect()
+}
+
+// Returns what's wrong with a nerd other than its power
+fn nerd_problems(nerd: &Nerd, roster: &[Nerd]) -> Vec<String> {
+    let mut problems = Vec::new();
+    if roster
+        .iter()
+        .filter(|other| other.name == nerd.name)
+        .count()
+        > 1
+    {
+        problems.push("Another nerd has the same name".to_string());
+    }
+    if nerd.name.chars().count() > MAX_NAME_LENGTH {
+        problems.push(format!(
+            "Its name is longer than {MAX_NAME_LENGTH} characters"
+        ));
+    }
+    if nerd.max_health < 1 {
+        problems.push("It has no health".to_string());
+    }
+    if !nerd
+        .actions
+        .iter()
+        .any(|action| action.action_type() == ActionType::Damage)
+    {
+        problems.push("It has no Damage action, so it can't knock anyone out".to_string());
+    }
+    for action in nerd.actions.iter().filter(|action| action.value() < 1) {
+        problems.push(format!("{} does nothing", action.base_name()));
+    }
+    for (i, action) in nerd.actions.iter().enumerate() {
+        if let Some(Err(err)) = action.script().map(|script| trial_run(script?, nerd, i)) {
+            problems.push(format!(
+                "{}'s script doesn't work: {err}",
+                action.base_name()
+            ));
+        }
+    }
+    let width = nerd
+        .sprite
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    if width > MAX_SPRITE_WIDTH {
+        problems.push(format!(
+            "Its sprite is {width} characters wide, but the layout only fits {MAX_SPRITE_WIDTH}"
+        ));
+    }
+    let lines = nerd.sprite.lines().count();
+    if lines > MAX_SPRITE_LINES {
+        problems.push(format!(
+            "Its sprite is {lines} lines tall, but the layout only fits {MAX_SPRITE_LINES}"
+        ));
+    }
+    problems
+}
+
+// Runs a script for an action used against the nerd itself (with and without a critical hit),
+// otherwise says what went wrong
+fn trial_run(script: &Script, nerd: &Nerd, action: usize) -> Result<(), String> {
+    for critical in [false, true] {
+        let (_, answer) = script.equation(nerd, nerd, action, critical)?;
+        let (mut attacker, mut defender) = (*nerd, *nerd);
+        script.apply(&mut attacker, &mut defender, action, critical, answer)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use acnd::http::{Request, Response};
+use acnd::lobby::Lobby;
+use std::env;
+use std::net::{TcpListener, TcpStream};
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// Settings used when they aren't given (only this machine can connect unless another address is given)
+const DEFAULT_ADDRESS: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 7879;
+const DEFAULT_TIMEOUT: u64 = 60;
+const DEFAULT_SPECTATOR_DELAY: u64 = 0;
+
+// How long to wait for a slow client to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How often players are checked for having gone quiet
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// Shown when the arguments are wrong
+const USAGE: &str = "Usage: server [--address ADDRESS] [--port PORT] [--timeout SECONDS] [--spectator-delay SECONDS]";
+
+// Hosts a lobby of rooms that players on other machines can play matches in
+fn main() {
+    let settings = match Settings::from_args(env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    let listener = match TcpListener::bind((settings.address.as_str(), settings.port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("The server couldn't be started: {err}");
+            process::exit(1);
+        }
+    };
+    println!(
+        "Hosting a lobby on http://{}:{} (players forfeit after {} seconds without a request)",
+        settings.address, settings.port, settings.timeout
+    );
+
+    let lobby = Arc::new(Mutex::new(Lobby::new(
+        Duration::from_secs(settings.timeout),
+        Duration::from_secs(settings.spectator_delay),
+    )));
+    let idle_lobby = Arc::clone(&lobby);
+    thread::spawn(move || loop {
+        thread::sleep(IDLE_CHECK_INTERVAL);
+        idle_lobby
+            .lock()
+            .expect("the lobby is usable")
+            .forfeit_idle();
+    });
+    for stream in listener.incoming().flatten() {
+        let lobby = Arc::clone(&lobby);
+        thread::spawn(move || answer(&stream, &lobby));
+    }
+}
+
+// Reads a request from a client and writes the lobby's response
+fn answer(stream: &TcpStream, lobby: &Mutex<Lobby>) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let response = match Request::read(stream) {
+        Ok(request) => lobby.lock().expect("the lobby is usable").handle(&request),
+        Err(_) => Response::error(400, "The request couldn't be read"),
+    };
+    let _ = response.write(stream);
+}
+
+// Settings read from the command line
+struct Settings {
+    address: String,
+    port: u16,
+    timeout: u64,
+    spectator_delay: u64,
+}
+
+impl Settings {
+    // Reads the settings from the arguments, otherwise says what's wrong with them
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut settings = Self {
+            address: DEFAULT_ADDRESS.to_string(),
+            port: DEFAULT_PORT,
+            timeout: DEFAULT_TIMEOUT,
+            spectator_delay: DEFAULT_SPECTATOR_DELAY,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--address" => {
+                    settings.address = args
+                        .next()
+                        .ok_or_else(|| format!("{arg} needs an address after it"))?
+                }
+                "--port" => settings.port = Self::value(&arg, args.next())?,
+                "--timeout" => settings.timeout = Self::value(&arg, args.next())?,
+                "--spectator-delay" => settings.spectator_delay = Self::value(&arg, args.next())?,
+                _ => return Err(format!("Unknown argument: {arg}")),
+            }
+        }
+        if settings.timeout == 0 {
+            return Err("The timeout has to be at least one second".to_string());
+        }
+        Ok(settings)
+    }
+
+    // Parses the value given after an argument
+    fn value<T: std::str::FromStr>(arg: &str, value: Option<String>) -> Result<T, String> {
+        value
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| format!("{arg} needs a number after it"))
+    }
+}
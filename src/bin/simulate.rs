@@ -0,0 +1,250 @@
+use acnd::ai::Ai;
+use acnd::duel::Duel;
+use acnd::nerds::NERDS;
+use std::env;
+use std::process;
+
+// Settings used when they aren't given
+const DEFAULT_MATCHES: usize = 1000;
+const DEFAULT_ACCURACY: i32 = 80;
+
+// Turns after which a match is counted as a draw
+const MAX_TURNS: usize = 1000;
+
+// Shown when the arguments are wrong
+const USAGE: &str = "Usage: simulate [--matches N] [--accuracy PERCENT] [--seed N] [--csv]";
+
+// Width of the columns in the printed tables
+const COLUMN_WIDTH: usize = 10;
+
+// Runs AI vs AI matches between every pair of nerds and prints how balanced they are
+fn main() {
+    let settings = match Settings::from_args(env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    if let Some(seed) = settings.seed {
+        fastrand::seed(seed);
+    }
+    let results = Results::simulate(&settings);
+    if settings.csv {
+        results.print_csv();
+    } else {
+        results.print_tables(&settings);
+    }
+}
+
+// Settings read from the command line
+struct Settings {
+    matches: usize,
+    accuracy: i32,
+    seed: Option<u64>,
+    csv: bool,
+}
+
+impl Settings {
+    // Reads the settings from the arguments, otherwise says what's wrong with them
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut settings = Self {
+            matches: DEFAULT_MATCHES,
+            accuracy: DEFAULT_ACCURACY,
+            seed: None,
+            csv: false,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--matches" => settings.matches = Self::value(&arg, args.next())?,
+                "--accuracy" => settings.accuracy = Self::value(&arg, args.next())?,
+                "--seed" => settings.seed = Some(Self::value(&arg, args.next())?),
+                "--csv" => settings.csv = true,
+                _ => return Err(format!("Unknown argument: {arg}")),
+            }
+        }
+        if !(0..=100).contains(&settings.accuracy) {
+            return Err("The accuracy has to be between 0 and 100".to_string());
+        }
+        if settings.matches == 0 {
+            return Err("There has to be at least one match".to_string());
+        }
+        Ok(settings)
+    }
+
+    // Parses the value given after an argument
+    fn value<T: std::str::FromStr>(arg: &str, value: Option<String>) -> Result<T, String> {
+        value
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| format!("{arg} needs a number after it"))
+    }
+}
+
+// How the matches between two nerds went (for the first of them)
+#[derive(Copy, Clone, Default)]
+struct PairResults {
+    wins: usize,
+    losses: usize,
+    draws: usize,
+    turns: usize,
+}
+
+impl PairResults {
+    // Returns the results for the other nerd
+    fn flipped(&self) -> Self {
+        Self {
+            wins: self.losses,
+            losses: self.wins,
+            ..*self
+        }
+    }
+
+    // Returns the number of matches played
+    fn matches(&self) -> usize {
+        self.wins + self.losses + self.draws
+    }
+
+    // Returns the percent of matches won
+    fn win_rate(&self) -> f64 {
+        self.wins as f64 * 100.0 / self.matches() as f64
+    }
+
+    // Returns the average number of turns a match took
+    fn average_turns(&self) -> f64 {
+        self.turns as f64 / self.matches() as f64
+    }
+}
+
+// Everything recorded over every match
+struct Results {
+    pairs: Vec<Vec<PairResults>>,
+    action_uses: Vec<[usize; 4]>,
+}
+
+impl Results {
+    // Plays the matches between every pair of nerds (each going first in half of them)
+    fn simulate(settings: &Settings) -> Self {
+        let ai = Ai::new(settings.accuracy);
+        let mut results = Self {
+            pairs: vec![vec![PairResults::default(); NERDS.len()]; NERDS.len()],
+            action_uses: vec![[0; 4]; NERDS.len()],
+        };
+        for first in 0..NERDS.len() {
+            for second in first..NERDS.len() {
+                let mut pair = PairResults::default();
+                for i in 0..settings.matches {
+                    let first_index = i % 2;
+                    let order = if first_index == 0 {
+                        [first, second]
+                    } else {
+                        [second, first]
+                    };
+                    let (winner, turns) = results.play(order, ai);
+                    match winner.map(|winner| winner == first_index) {
+                        Some(true) => pair.wins += 1,
+                        Some(false) => pair.losses += 1,
+                        None => pair.draws += 1,
+                    }
+                    pair.turns += turns;
+                }
+                results.pairs[second][first] = pair.flipped();
+                results.pairs[first][second] = pair;
+            }
+        }
+        results
+    }
+
+    // Plays a match between two roster nerds; returns the winner (if there is one) and the turns taken
+    fn play(&mut self, order: [usize; 2], ai: Ai) -> (Option<usize>, usize) {
+        let mut duel = Duel::new([NERDS[order[0]], NERDS[order[1]]], 0);
+        duel.adaptive = false;
+        for turn in 0..MAX_TURNS {
+            if let Some(winner) = duel.winner() {
+                return (Some(winner), turn);
+            }
+            let action = ai.choose_action(&duel);
+            self.action_uses[order[duel.current_nerd]][action] += 1;
+            if duel.choose_action(action) {
+                duel.submit(ai.answer(&duel));
+            }
+        }
+        (duel.winner(), MAX_TURNS)
+    }
+
+    // Returns the percent of a nerd's turns that each of their actions was used
+    fn action_rates(&self, nerd: usize) -> [f64; 4] {
+        let uses = self.action_uses[nerd];
+        let total = uses.iter().sum::<usize>().max(1) as f64;
+        uses.map(|uses| uses as f64 * 100.0 / total)
+    }
+
+    // Prints the results as tables
+    fn print_tables(&self, settings: &Settings) {
+        println!(
+            "Win rates of each row's nerd against each column's nerd ({} matches each, {}% accuracy):",
+            settings.matches, settings.accuracy
+        );
+        self.print_matrix(|pair| format!("{:.1}%", pair.win_rate()));
+
+        println!("\nAverage match length in turns:");
+        self.print_matrix(|pair| format!("{:.1}", pair.average_turns()));
+
+        println!("\nAction usage:");
+        for (i, nerd) in NERDS.iter().enumerate() {
+            println!("{}", nerd.name);
+            for (action, rate) in nerd.actions.iter().zip(self.action_rates(i)) {
+                println!("  {:<30}{rate:>6.1}%", action.base_name());
+            }
+        }
+    }
+
+    // Prints a value for every pair of nerds as a table
+    fn print_matrix(&self, value: impl Fn(&PairResults) -> String) {
+        print!("{:COLUMN_WIDTH$}", "");
+        for nerd in &NERDS {
+            print!("{:>COLUMN_WIDTH$}", nerd.name);
+        }
+        println!();
+        for (nerd, pairs) in NERDS.iter().zip(&self.pairs) {
+            print!("{:COLUMN_WIDTH$}", nerd.name);
+            for pair in pairs {
+                print!("{:>COLUMN_WIDTH$}", value(pair));
+            }
+            println!();
+        }
+    }
+
+    // Prints the results as CSV (a row for every pair of nerds, then a row for every action)
+    fn print_csv(&self) {
+        println!("nerd,opponent,matches,wins,losses,draws,win_rate,average_turns");
+        for (nerd, pairs) in NERDS.iter().zip(&self.pairs) {
+            for (opponent, pair) in NERDS.iter().zip(pairs) {
+                println!(
+                    "{},{},{},{},{},{},{:.2},{:.2}",
+                    nerd.name,
+                    opponent.name,
+                    pair.matches(),
+                    pair.wins,
+                    pair.losses,
+                    pair.draws,
+                    pair.win_rate(),
+                    pair.average_turns()
+                );
+            }
+        }
+
+        println!("\nnerd,action,uses,usage_rate");
+        for (i, nerd) in NERDS.iter().enumerate() {
+            let rates = self.action_rates(i);
+            for (j, action) in nerd.actions.iter().enumerate() {
+                println!(
+                    "{},{},{},{:.2}",
+                    nerd.name,
+                    action.base_name(),
+                    self.action_uses[i][j],
+                    rates[j]
+                );
+            }
+        }
+    }
+}
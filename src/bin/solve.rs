@@ -0,0 +1,105 @@
+use acnd::nerds::{Nerd, Nerds, NERDS};
+use acnd::solver::{self, Analysis};
+use std::env;
+use std::process;
+
+// Settings used when they aren't given
+const DEFAULT_DEPTH: usize = 6;
+const DEFAULT_TURNS: usize = 30;
+
+// Shown when the arguments are wrong
+const USAGE: &str = "Usage: solve FIRST_NERD SECOND_NERD [--depth TURNS] [--turns TURNS]";
+
+// Finds the best play between two nerds and prints it
+fn main() {
+    let settings = match Settings::from_args(env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    let mut nerds = settings.nerds;
+    let analysis = Analysis::new(&nerds, 0, settings.depth);
+    println!(
+        "{} (going first) vs {}, searching {} turns ahead with every answer right",
+        nerds[0].name, nerds[1].name, settings.depth
+    );
+    println!(
+        "{}'s chance of winning with the best play: {:.1}%",
+        nerds[0].name,
+        analysis.win_chance * 100.0
+    );
+
+    println!("\nRecommended actions (if there are no critical hits):");
+    let mut current_nerd = 0;
+    for turn in 1..=settings.turns {
+        let analysis = Analysis::new(&nerds, current_nerd, settings.depth);
+        let nerd = nerds[current_nerd];
+        println!(
+            "Turn {turn}: {} uses {} ({:.1}% chance of winning)",
+            nerd.name,
+            nerd.actions[analysis.action].name(),
+            analysis.win_chance * 100.0
+        );
+        nerds = solver::after_action(&nerds, current_nerd, analysis.action, false);
+        if let Some(loser) = nerds.iter().find(|nerd| nerd.health < 1) {
+            println!("{} is knocked out", loser.name);
+            return;
+        }
+        current_nerd = 1 - current_nerd;
+    }
+    println!(
+        "Nobody is knocked out after {} turns ({} has {} health, {} has {} health)",
+        settings.turns, nerds[0].name, nerds[0].health, nerds[1].name, nerds[1].health
+    );
+}
+
+// Settings read from the command line
+struct Settings {
+    nerds: Nerds,
+    depth: usize,
+    turns: usize,
+}
+
+impl Settings {
+    // Reads the settings from the arguments, otherwise says what's wrong with them
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut names = Vec::new();
+        let mut depth = DEFAULT_DEPTH;
+        let mut turns = DEFAULT_TURNS;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--depth" => depth = Self::value(&arg, args.next())?,
+                "--turns" => turns = Self::value(&arg, args.next())?,
+                _ => names.push(arg),
+            }
+        }
+        let [first, second] = names.as_slice() else {
+            return Err("Two nerds have to be given".to_string());
+        };
+        if depth == 0 {
+            return Err("The search has to look at least one turn ahead".to_string());
+        }
+        Ok(Self {
+            nerds: [Self::nerd(first)?, Self::nerd(second)?],
+            depth,
+            turns,
+        })
+    }
+
+    // Returns the roster nerd with a name (ignoring case)
+    fn nerd(name: &str) -> Result<Nerd, String> {
+        NERDS
+            .into_iter()
+            .find(|nerd| nerd.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("There is no nerd named {name}"))
+    }
+
+    // Parses the value given after an argument
+    fn value(arg: &str, value: Option<String>) -> Result<usize, String> {
+        value
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| format!("{arg} needs a number after it"))
+    }
+}
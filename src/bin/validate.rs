@@ -0,0 +1,87 @@
+use acnd::balance::{self, Report, MAX_POWER, MIN_POWER};
+use acnd::creator;
+use std::env;
+use std::process;
+
+// Shown when the arguments are wrong
+const USAGE: &str = "Usage: validate [--min POWER] [--max POWER] [--skip NERD]...";
+
+// Checks every nerd (created ones included) and exits with an error if any of them have problems
+fn main() {
+    let settings = match Settings::from_args(env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    let roster = creator::roster();
+    let reports: Vec<Report> = balance::validate(&roster, settings.min_power, settings.max_power)
+        .into_iter()
+        .filter(|report| {
+            !settings
+                .skipped
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(report.name))
+        })
+        .collect();
+    for report in &reports {
+        println!("{}: power {}", report.name, report.power);
+        for problem in &report.problems {
+            println!("  {problem}");
+        }
+    }
+
+    let flagged = reports
+        .iter()
+        .filter(|report| !report.problems.is_empty())
+        .count();
+    if flagged > 0 {
+        println!("{flagged} of {} nerds have problems", reports.len());
+        process::exit(1);
+    }
+    println!(
+        "Every nerd is inside of the power band of {} to {}",
+        settings.min_power, settings.max_power
+    );
+}
+
+// Settings read from the command line
+struct Settings {
+    min_power: i32,
+    max_power: i32,
+    skipped: Vec<String>,
+}
+
+impl Settings {
+    // Reads the settings from the arguments, otherwise says what's wrong with them
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut settings = Self {
+            min_power: MIN_POWER,
+            max_power: MAX_POWER,
+            skipped: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--min" => settings.min_power = Self::value(&arg, args.next())?,
+                "--max" => settings.max_power = Self::value(&arg, args.next())?,
+                "--skip" => settings.skipped.push(
+                    args.next()
+                        .ok_or_else(|| format!("{arg} needs a nerd after it"))?,
+                ),
+                _ => return Err(format!("Unknown argument: {arg}")),
+            }
+        }
+        if settings.min_power > settings.max_power {
+            return Err("The minimum power can't be higher than the maximum".to_string());
+        }
+        Ok(settings)
+    }
+
+    // Parses the value given after an argument
+    fn value(arg: &str, value: Option<String>) -> Result<i32, String> {
+        value
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| format!("{arg} needs a number after it"))
+    }
+}
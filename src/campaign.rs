@@ -0,0 +1,209 @@
+use crate::ai::Ai;
+use crate::game::fill_names;
+use crate::nerds::{Nerd, Nerds, NERDS};
+use crate::storage::Save;
+use crate::unlocks::{Unlockable, Unlocks};
+
+// Name of the file the campaign progress is saved in
+const SAVE_NAME: &str = "campaign";
+
+// Lines said before and after fighting each nerd of the roster (nerd0 is the player, nerd1 the opponent)
+const SCENES: [Scene; 5] = [
+    Scene::new(
+        &[
+            "nerd1 is blocking the hallway, reading a math textbook for fun.",
+            "nerd1: \"Move? Only if you can beat me at integer operations.\"",
+        ],
+        &[
+            "nerd1: \"My textbook... it has a stain on it now.\"",
+            "nerd0 steps over nerd1 and continues down the hallway.",
+        ],
+    ),
+    Scene::new(
+        &[
+            "nerd1 draws a meter ruler like a katana.",
+            "nerd1: \"I have trained in the ancient art of measurement. Prepare yourself.\"",
+        ],
+        &[
+            "The meter ruler snaps in half. nerd1 weeps quietly.",
+            "nerd1: \"It was only 30 centimeters anyway...\"",
+        ],
+    ),
+    Scene::new(
+        &[
+            "nerd1 is lifting inflatable dumbbells in the gym.",
+            "nerd1: \"Nobody gets past me without doing their homework first.\"",
+        ],
+        &[
+            "nerd1 deflates, along with the dumbbells.",
+            "nerd1: \"Fine. Go. But the final boss is waiting in the principal's office.\"",
+        ],
+    ),
+    Scene::new(
+        &[
+            "nerd1 is watching a Dhar Mann video in the library.",
+            "nerd1: \"I'm telling the teacher you're fighting! Unless... you fight me first.\"",
+        ],
+        &[
+            "nerd1: \"This is going in my diary.\"",
+            "nerd1 runs off to tell the teacher, who is conveniently on a coffee break.",
+        ],
+    ),
+    Scene::new(
+        &[
+            "The lights flicker. nerd1 is sitting on the principal's chair, doing absolutely nothing.",
+            "nerd1: \"You've made it this far. I'll go easy on you. I'll only do basically nothing.\"",
+        ],
+        &[
+            "nerd1: \"Impossible... I did literally nothing and still lost.\"",
+            "nerd0 is the new champion of the AC Nerd Duels.",
+        ],
+    ),
+];
+
+// Lines said when the player loses a fight (they get to try again)
+const DEFEAT_SCENE: [&str; 2] = [
+    "nerd1: \"Maybe you should've paid attention in class.\"",
+    "nerd0 wakes up in the nurse's office, ready for a rematch.",
+];
+
+// Lines said after the whole campaign is beaten
+const ENDING_SCENE: [&str; 2] = [
+    "The school bell rings. Every nerd in the school knows the name nerd0.",
+    "nerd0 still has to do the actual math homework though. The end.",
+];
+
+// The nerd fought as the final boss (the last opponent stands in if the player picked them), and the
+// boss's stats
+const BOSS: &str = "Yamin";
+const BOSS_HEALTH: i32 = 500;
+const BOSS_ACTION_VALUE: i32 = 4;
+
+// How often the opponents answer right (out of 100), getting better every fight
+const BASE_ACCURACY: i32 = 60;
+const ACCURACY_STEP: i32 = 10;
+const MAX_ACCURACY: i32 = 95;
+
+// Lines said before and after fighting a nerd
+struct Scene {
+    intro: &'static [&'static str],
+    outro: &'static [&'static str],
+}
+
+impl Scene {
+    // Creates a new scene
+    const fn new(intro: &'static [&'static str], outro: &'static [&'static str]) -> Self {
+        Self { intro, outro }
+    }
+}
+
+// A single player story where the player fights the rest of the roster in order
+pub struct Campaign {
+    nerd: usize,
+    stage: usize,
+}
+
+impl Campaign {
+    // Starts a new campaign with the chosen nerd
+    pub fn new(nerd: &Nerd) -> Self {
+        Self {
+            nerd: Nerd::roster_index(nerd.name).unwrap_or(0),
+            stage: 0,
+        }
+    }
+
+    // Loads the saved campaign progress if there is any
+    pub fn load() -> Option<Self> {
+        let save = Save::load(SAVE_NAME)?;
+        let nerd = Nerd::roster_index(save.get("nerd")?)?;
+        let campaign = Self {
+            nerd,
+            stage: save.get_parsed("stage")?,
+        };
+        (campaign.stage < campaign.opponents().len()).then_some(campaign)
+    }
+
+    // Saves the campaign progress
+    pub fn save(&self) {
+        let mut save = Save::new();
+        save.set("nerd", NERDS[self.nerd].name);
+        save.set("stage", self.stage);
+        let _ = save.write(SAVE_NAME);
+    }
+
+    // Deletes the saved campaign progress
+    pub fn delete() {
+        let _ = Save::remove(SAVE_NAME);
+    }
+
+    // Returns the roster indices of the opponents in order (every starting nerd, then the boss unless
+    // it's the player's nerd); the last one is fought as the boss
+    fn opponents(&self) -> Vec<usize> {
+        (0..NERDS.len())
+            .filter(|&i| i != self.nerd && Unlocks::is_starting(Unlockable::Nerd(NERDS[i].name)))
+            .chain(Nerd::roster_index(BOSS).filter(|&boss| boss != self.nerd))
+            .collect()
+    }
+
+    // Returns the number of the current fight and the number of fights
+    pub fn progress(&self) -> (usize, usize) {
+        (self.stage + 1, self.opponents().len())
+    }
+
+    // Returns the player and opponent nerds of the current fight, with numbers growing every fight
+    pub fn nerds(&self) -> Nerds {
+        let factor = self.stage as i32 + 1;
+        let opponents = self.opponents();
+        let opponent = opponents[self.stage];
+        let opponent = if self.stage + 1 == opponents.len() {
+            NERDS[opponent].weakened(BOSS_HEALTH, BOSS_ACTION_VALUE)
+        } else {
+            NERDS[opponent]
+        };
+        [NERDS[self.nerd].scaled(factor), opponent.scaled(factor)]
+    }
+
+    // Returns the AI controlling the opponent of the current fight
+    pub fn ai(&self) -> Ai {
+        Ai::new((BASE_ACCURACY + ACCURACY_STEP * self.stage as i32).min(MAX_ACCURACY))
+    }
+
+    // Returns the lines said before the current fight
+    pub fn intro(&self) -> Vec<String> {
+        self.lines(SCENES[self.opponents()[self.stage]].intro)
+    }
+
+    // Returns the lines said after winning the current fight
+    pub fn outro(&self) -> Vec<String> {
+        self.lines(SCENES[self.opponents()[self.stage]].outro)
+    }
+
+    // Returns the lines said after losing the current fight
+    pub fn defeat(&self) -> Vec<String> {
+        self.lines(&DEFEAT_SCENE)
+    }
+
+    // Returns the lines said after finishing the campaign
+    pub fn ending(&self) -> Vec<String> {
+        self.lines(&ENDING_SCENE)
+    }
+
+    // Fills in the nerd names of the current fight in lines of dialogue
+    fn lines(&self, lines: &[&str]) -> Vec<String> {
+        let names = [
+            NERDS[self.nerd].name,
+            NERDS[self.opponents()[self.stage]].name,
+        ];
+        lines.iter().map(|line| fill_names(line, names)).collect()
+    }
+
+    // Moves on to the next fight; returns whether the campaign is finished
+    pub fn advance(&mut self) -> bool {
+        if self.stage + 1 < self.opponents().len() {
+            self.stage += 1;
+            false
+        } else {
+            true
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use crate::balance;
+use crate::nerds::{Action, ActionType, Nerd, ACTION_TYPES, NERDS, SUBJECTS};
+use crate::script;
+use crate::storage::Save;
+use std::fs;
+use std::sync::Mutex;
+
+// Name of the save file that created nerds are kept in
+const SAVE_NAME: &str = "nerds";
+
+// Power that a created nerd's health and actions can add up to (so they stay balanced)
+const BUDGET: i32 = balance::MAX_POWER;
+
+// Limits of what can be chosen for a created nerd
+const MIN_HEALTH: i32 = 50;
+const MAX_HEALTH: i32 = 1000;
+const HEALTH_STEP: i32 = 50;
+const MIN_VALUE: i32 = 1;
+const MAX_VALUE: i32 = 9;
+pub const MAX_NAME_LENGTH: usize = 20;
+const MAX_ACTION_NAME_LENGTH: usize = 30;
+pub const MAX_SPRITE_WIDTH: usize = 20;
+pub const MAX_SPRITE_LINES: usize = 10;
+
+// Characters that can't be in names (they separate values in save files)
+const RESERVED_CHARS: [char; 2] = ['|', '='];
+
+// Stats that created nerds all have
+const SPEED: i32 = 5;
+const BIO: &str = "A homemade nerd, fresh out of the nerd creator.";
+
+// What a new nerd starts with in the creator
+const DEFAULT_HEALTH: i32 = 200;
+const DEFAULT_ACTIONS: [(&str, ActionType, i32); 4] = [
+    ("Punch", ActionType::Damage, 2),
+    ("Snack", ActionType::Heal, 2),
+    ("Trip", ActionType::Weaken, 1),
+    ("Study", ActionType::Strengthen, 1),
+];
+
+// Items of the creator menu that aren't actions (each action has a name, type and value after them)
+const NAME_ITEM: usize = 0;
+const SUBJECT_ITEM: usize = 1;
+const HEALTH_ITEM: usize = 2;
+const SPRITE_ITEM: usize = 3;
+const SPRITE_FILE_ITEM: usize = 4;
+const FIRST_ACTION_ITEM: usize = 5;
+const ACTION_ITEMS: usize = 3;
+const SAVE_ITEM: usize = FIRST_ACTION_ITEM + ACTION_ITEMS * 4;
+
+// Created nerds once they've been loaded (so their text is only leaked once)
+static CREATED_NERDS: Mutex<Option<Vec<Nerd>>> = Mutex::new(None);
+
+// Returns the roster nerds followed by the created ones
+pub fn roster() -> Vec<Nerd> {
+    NERDS.into_iter().chain(load_nerds()).collect()
+}
+
+// Returns every created nerd, loading them from their save file the first time
+// (their text is leaked, since nerds only hold text that lasts the whole program)
+pub fn load_nerds() -> Vec<Nerd> {
+    let mut created = CREATED_NERDS.lock().unwrap_or_else(|err| err.into_inner());
+    created
+        .get_or_insert_with(|| {
+            let Some(save) = Save::load(SAVE_NAME) else {
+                return Vec::new();
+            };
+            save.get_all("nerd")
+                .filter_map(|name| load_nerd(&save, name))
+                .collect()
+        })
+        .clone()
+}
+
+// Reads one created nerd from the save file (an action can name a script file after its value,
+// and it's a normal action if the script file can't be read); nerds over the budget aren't loaded,
+// so editing the file can't make one stronger than the creator allows
+fn load_nerd(save: &Save, name: &str) -> Option<Nerd> {
+    let subject = save.get(&format!("{name}.subject"))?;
+    let subject = *SUBJECTS.iter().find(|s| s.name() == subject)?;
+    let health = save.get_parsed(&format!("{name}.health"))?;
+    let (sprite_key, action_key) = (format!("{name}.sprite"), format!("{name}.action"));
+    let sprite: Vec<&str> = save.get_all(&sprite_key).collect();
+    let actions: Vec<Action> = save
+        .get_all(&action_key)
+        .filter_map(|text| {
+            let mut parts = text.split('|');
+            let action_name = parts.next()?;
+            let action_type = parts.next()?;
+            let action_type = *ACTION_TYPES.iter().find(|t| t.name() == action_type)?;
+            let value = parts.next()?.parse().ok()?;
+            let action = Action::new(leak(action_name), action_type, value, subject);
+            match parts.next().and_then(|script| script::load(script.trim())) {
+                Some(script) => Some(action.with_script(script)),
+                None => Some(action),
+            }
+        })
+        .collect();
+    let nerd = Nerd::new(
+        leak(name),
+        BIO,
+        subject,
+        health,
+        SPEED,
+        actions.try_into().ok()?,
+        leak(&sprite.join("\n")),
+    );
+    (balance::power(&nerd) <= BUDGET).then_some(nerd)
+}
+
+// Keeps text around for the rest of the program
+fn leak(text: &str) -> &'static str {
+    Box::leak(text.to_string().into_boxed_str())
+}
+
+// A nerd being made in the creator
+pub struct Creator {
+    name: String,
+    subject: usize,
+    health: i32,
+    sprite: String,
+    sprite_file: String,
+    actions: [(String, usize, i32); 4],
+    message: String,
+}
+
+impl Creator {
+    // Starts making a nerd with the default stats
+    pub fn new() -> Self {
+        Self {
+            name: String::new(),
+            subject: 0,
+            health: DEFAULT_HEALTH,
+            sprite: String::new(),
+            sprite_file: String::new(),
+            actions: DEFAULT_ACTIONS.map(|(name, action_type, value)| {
+                let action_type = ACTION_TYPES
+                    .iter()
+                    .position(|&t| t == action_type)
+                    .unwrap_or(0);
+                (name.to_string(), action_type, value)
+            }),
+            message: String::new(),
+        }
+    }
+
+    // Returns the text of each item in the creator menu
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Name: {}", self.name),
+            format!("Subject: {}", SUBJECTS[self.subject].name()),
+            format!("Health: {}", self.health),
+            format!(
+                "Sprite: {} lines (type it, enter for a new line)",
+                self.sprite.lines().count()
+            ),
+            format!("Sprite file: {}", self.sprite_file),
+        ];
+        for (i, (name, action_type, value)) in self.actions.iter().enumerate() {
+            lines.extend([
+                format!("Action {} name: {name}", i + 1),
+                format!(
+                    "Action {} type: {}",
+                    i + 1,
+                    ACTION_TYPES[*action_type].name()
+                ),
+                format!("Action {} value: {value}", i + 1),
+            ]);
+        }
+        lines.push("Save".to_string());
+        lines
+    }
+
+    // Returns the points spent and the last thing that happened
+    pub fn info(&self) -> Vec<String> {
+        vec![
+            format!("Points: {}/{BUDGET}", balance::power(&self.nerd())),
+            self.message.clone(),
+        ]
+    }
+
+    // Returns the sprite drawn so far
+    pub fn sprite(&self) -> &str {
+        &self.sprite
+    }
+
+    // Changes an item to its next or previous value (if that stays inside the budget)
+    pub fn change(&mut self, item: usize, forward: bool) {
+        let (subject, health, actions) = (self.subject, self.health, self.actions.clone());
+        let step = if forward { 1 } else { -1 };
+        match (item, action_item(item)) {
+            (SUBJECT_ITEM, _) => self.subject = cycle(self.subject, SUBJECTS.len(), forward),
+            (HEALTH_ITEM, _) => {
+                self.health = (self.health + HEALTH_STEP * step).clamp(MIN_HEALTH, MAX_HEALTH)
+            }
+            (_, Some((action, 1))) => {
+                let action_type = &mut self.actions[action].1;
+                *action_type = cycle(*action_type, ACTION_TYPES.len(), forward);
+            }
+            (_, Some((action, 2))) => {
+                let value = &mut self.actions[action].2;
+                *value = (*value + step).clamp(MIN_VALUE, MAX_VALUE);
+            }
+            _ => return,
+        }
+        if balance::power(&self.nerd()) > BUDGET {
+            (self.subject, self.health, self.actions) = (subject, health, actions);
+            self.message = format!("That would go over the budget of {BUDGET} points");
+        } else {
+            self.message.clear();
+        }
+    }
+
+    // Types text into an item (if it holds text)
+    pub fn type_text(&mut self, item: usize, text: &str) {
+        let (field, max_length) = match (item, action_item(item)) {
+            (NAME_ITEM, _) => (&mut self.name, MAX_NAME_LENGTH),
+            (SPRITE_ITEM, _) => {
+                let line = self.sprite.rsplit('\n').next().unwrap_or_default();
+                let max_length =
+                    self.sprite.chars().count() - line.chars().count() + MAX_SPRITE_WIDTH;
+                (&mut self.sprite, max_length)
+            }
+            (SPRITE_FILE_ITEM, _) => (&mut self.sprite_file, usize::MAX),
+            (_, Some((action, 0))) => (&mut self.actions[action].0, MAX_ACTION_NAME_LENGTH),
+            _ => return,
+        };
+        for c in text.chars() {
+            if field.chars().count() < max_length
+                && (item == SPRITE_ITEM || !RESERVED_CHARS.contains(&c))
+            {
+                field.push(c);
+            }
+        }
+    }
+
+    // Erases the last character of an item (if it holds text)
+    pub fn erase(&mut self, item: usize) {
+        match (item, action_item(item)) {
+            (NAME_ITEM, _) => self.name.pop(),
+            (SPRITE_ITEM, _) => self.sprite.pop(),
+            (SPRITE_FILE_ITEM, _) => self.sprite_file.pop(),
+            (_, Some((action, 0))) => self.actions[action].0.pop(),
+            _ => None,
+        };
+    }
+
+    // Does what enter does on an item (a new sprite line, loading the sprite file or saving);
+    // returns the nerd if it was saved
+    pub fn enter(&mut self, item: usize, roster: &[Nerd]) -> Option<Nerd> {
+        match item {
+            SPRITE_ITEM if self.sprite.split('\n').count() < MAX_SPRITE_LINES => {
+                self.sprite.push('\n')
+            }
+            SPRITE_FILE_ITEM => self.load_sprite_file(),
+            SAVE_ITEM => match self.save(roster) {
+                Ok(nerd) => {
+                    *self = Self::new();
+                    self.message = format!("{} was saved and can now be picked", nerd.name);
+                    return Some(nerd);
+                }
+                Err(err) => self.message = err,
+            },
+            _ => (),
+        }
+        None
+    }
+
+    // Replaces the sprite with the text of the sprite file
+    fn load_sprite_file(&mut self) {
+        let Ok(text) = fs::read_to_string(self.sprite_file.trim()) else {
+            self.message = "The sprite file couldn't be read".to_string();
+            return;
+        };
+        let lines: Vec<&str> = text.trim_end().lines().collect();
+        if lines.len() > MAX_SPRITE_LINES
+            || lines
+                .iter()
+                .any(|line| line.chars().count() > MAX_SPRITE_WIDTH)
+        {
+            self.message = format!(
+                "Sprites can be at most {MAX_SPRITE_WIDTH} characters wide and {MAX_SPRITE_LINES} lines tall"
+            );
+            return;
+        }
+        self.sprite = lines.join("\n");
+        self.message = "The sprite was loaded".to_string();
+    }
+
+    // Checks the nerd and adds it to the save file, otherwise says what's wrong with it
+    fn save(&self, roster: &[Nerd]) -> Result<Nerd, String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("The nerd needs a name".to_string());
+        } else if roster
+            .iter()
+            .any(|nerd| nerd.name.eq_ignore_ascii_case(name))
+        {
+            return Err(format!("There is already a nerd named {name}"));
+        } else if self.sprite.trim().is_empty() {
+            return Err("The nerd needs a sprite".to_string());
+        } else if self
+            .actions
+            .iter()
+            .any(|(name, _, _)| name.trim().is_empty())
+        {
+            return Err("Every action needs a name".to_string());
+        }
+
+        let mut save = Save::load(SAVE_NAME).unwrap_or_default();
+        save.set("nerd", name);
+        save.set(&format!("{name}.subject"), SUBJECTS[self.subject].name());
+        save.set(&format!("{name}.health"), self.health);
+        for line in self.sprite.trim_end().lines() {
+            save.set(&format!("{name}.sprite"), line);
+        }
+        for (action_name, action_type, value) in &self.actions {
+            save.set(
+                &format!("{name}.action"),
+                format!(
+                    "{}|{}|{value}",
+                    action_name.trim(),
+                    ACTION_TYPES[*action_type].name()
+                ),
+            );
+        }
+        save.write(SAVE_NAME)
+            .map_err(|_| "The nerd couldn't be saved".to_string())?;
+        let nerd =
+            load_nerd(&save, name).ok_or_else(|| "The nerd couldn't be saved".to_string())?;
+        let mut created = CREATED_NERDS.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(created) = created.as_mut() {
+            created.push(nerd);
+        }
+        Ok(nerd)
+    }
+
+    // Returns the nerd with the chosen stats (without leaking its text, so only the numbers are right)
+    fn nerd(&self) -> Nerd {
+        let subject = SUBJECTS[self.subject];
+        let actions = self.actions.clone().map(|(_, action_type, value)| {
+            Action::new("", ACTION_TYPES[action_type], value, subject)
+        });
+        Nerd::new("", BIO, subject, self.health, SPEED, actions, "")
+    }
+}
+
+// Returns which action an item belongs to and which part of it (0 for the name, 1 for the type
+// and 2 for the value), if it's one of an action's items
+fn action_item(item: usize) -> Option<(usize, usize)> {
+    let offset = item.checked_sub(FIRST_ACTION_ITEM)?;
+    (item < SAVE_ITEM).then_some((offset / ACTION_ITEMS, offset % ACTION_ITEMS))
+}
+
+// Returns the next or previous index out of a number of values
+fn cycle(index: usize, len: usize, forward: bool) -> usize {
+    if forward {
+        (index + 1) % len
+    } else {
+        (index + len - 1) % len
+    }
+}
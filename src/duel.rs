@@ -0,0 +1,459 @@
+use crate::equation::{Difficulty, Equation, Mistake, MISTAKES};
+use crate::game::{self, GAME_START_MESSAGE};
+use crate::nerds::{Nerd, Nerds};
+use crate::options::Options;
+use crate::storage::Save;
+
+// Chance (out of 100) of getting an extra turn for each point of speed more than the other nerd
+const EXTRA_TURN_CHANCE: i32 = 10;
+const MAX_EXTRA_TURN_CHANCE: i32 = 50;
+
+// The rules and state of a single duel between two nerds
+pub struct Duel {
+    pub nerds: Nerds,
+    pub current_nerd: usize,
+    pub difficulties: [Difficulty; 2],
+    pub adaptive: bool,
+    pub extra_turns: bool,
+    pub multiple_choice: bool,
+    pub missing_numbers: bool,
+    starting_nerds: Nerds,
+    messages: Vec<String>,
+    action_selected: usize,
+    equation: String,
+    answer: i32,
+    result: i32,
+    missing: bool,
+    choices: Vec<(i32, Option<Mistake>)>,
+    critical: bool,
+    taunts: [Option<usize>; 2],
+}
+
+impl Duel {
+    // Starts a new duel between two nerds with one of them going first
+    pub fn new(nerds: Nerds, first_nerd: usize) -> Self {
+        Self {
+            nerds,
+            current_nerd: first_nerd,
+            difficulties: [Difficulty::new(); 2],
+            adaptive: true,
+            extra_turns: false,
+            multiple_choice: false,
+            missing_numbers: false,
+            starting_nerds: nerds,
+            messages: Vec::new(),
+            action_selected: 0,
+            equation: String::new(),
+            answer: 0,
+            result: 0,
+            missing: false,
+            choices: Vec::new(),
+            critical: nerds[first_nerd].roll_critical(),
+            taunts: [None; 2],
+        }
+    }
+
+    // Starts a new duel played with the rules in the options, announcing who goes first and why
+    // (the loser of the last duel can be given the first turn)
+    pub fn start(nerds: Nerds, options: &Options, last_loser: Option<usize>) -> Self {
+        let (first_nerd, reason) = options.first_turn.first_nerd(&nerds, last_loser);
+        let mut duel = Self::new(nerds, first_nerd);
+        duel.add_message(&game::fill_names(
+            GAME_START_MESSAGE,
+            [nerds[0].name, nerds[1].name],
+        ));
+        duel.add_message(&format!("{} goes first {reason}", nerds[first_nerd].name));
+        duel.adaptive = options.adaptive_difficulty;
+        duel.extra_turns = options.extra_turns;
+        duel.multiple_choice = options.multiple_choice;
+        duel.missing_numbers = options.missing_numbers;
+        duel
+    }
+
+    // Writes everything about the duel to a save
+    pub fn save(&self, save: &mut Save) {
+        for nerd in &self.nerds {
+            save.set("nerd", nerd.save_text());
+        }
+        for nerd in &self.starting_nerds {
+            save.set("starting_nerd", nerd.save_text());
+        }
+        for difficulty in &self.difficulties {
+            save.set("difficulty", difficulty.save_text());
+        }
+        save.set("current_nerd", self.current_nerd);
+        save.set("adaptive", self.adaptive);
+        save.set("extra_turns", self.extra_turns);
+        save.set("multiple_choice", self.multiple_choice);
+        save.set("missing_numbers", self.missing_numbers);
+        save.set("action_selected", self.action_selected);
+        save.set("equation", &self.equation);
+        save.set("answer", self.answer);
+        save.set("result", self.result);
+        save.set("missing", self.missing);
+        for (choice, mistake) in &self.choices {
+            let mistake = mistake.map_or("right", |mistake| mistake.save_name());
+            save.set("choice", format!("{choice} {mistake}"));
+        }
+        save.set("critical", self.critical);
+        for message in &self.messages {
+            save.set("message", message);
+        }
+    }
+
+    // Reads a duel from a save
+    pub fn load(save: &Save) -> Option<Self> {
+        let nerds = Self::load_pair(save, "nerd", Nerd::from_save)?;
+        Some(Self {
+            nerds,
+            current_nerd: save.get_parsed::<usize>("current_nerd")?.min(1),
+            difficulties: Self::load_pair(save, "difficulty", Difficulty::from_save)?,
+            adaptive: save.get_parsed("adaptive")?,
+            extra_turns: save.get_parsed("extra_turns").unwrap_or(false),
+            multiple_choice: save.get_parsed("multiple_choice").unwrap_or(false),
+            missing_numbers: save.get_parsed("missing_numbers").unwrap_or(false),
+            starting_nerds: Self::load_pair(save, "starting_nerd", Nerd::from_save)
+                .unwrap_or(nerds),
+            messages: save.get_all("message").map(String::from).collect(),
+            action_selected: save.get_parsed::<usize>("action_selected")?.min(3),
+            equation: save.get("equation")?.to_string(),
+            answer: save.get_parsed("answer")?,
+            result: save
+                .get_parsed("result")
+                .or_else(|| save.get_parsed("answer"))?,
+            missing: save.get_parsed("missing").unwrap_or(false),
+            choices: save
+                .get_all("choice")
+                .filter_map(Self::load_choice)
+                .collect(),
+            critical: save.get_parsed("critical")?,
+            taunts: [None; 2],
+        })
+    }
+
+    // Reads a value for each nerd from a save
+    fn load_pair<T>(save: &Save, key: &str, parse: fn(&str) -> Option<T>) -> Option<[T; 2]> {
+        let mut values = save.get_all(key).map(parse);
+        Some([values.next()??, values.next()??])
+    }
+
+    // Reads a multiple choice answer and the mistake it comes from
+    fn load_choice(text: &str) -> Option<(i32, Option<Mistake>)> {
+        let (choice, mistake) = text.split_once(' ')?;
+        let mistake = MISTAKES
+            .into_iter()
+            .find(|other| other.save_name() == mistake);
+        Some((choice.parse().ok()?, mistake))
+    }
+
+    // Returns the nerds as they were at the start of the duel
+    pub fn starting_nerds(&self) -> Nerds {
+        self.starting_nerds
+    }
+
+    // Returns every message about what has happened in the duel
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+
+    // Adds a message about what has happened in the duel
+    pub fn add_message(&mut self, text: &str) {
+        self.messages.push(text.to_string());
+    }
+
+    // Returns whether a nerd can still taunt this turn (each nerd can taunt once a turn)
+    pub fn can_taunt(&self, nerd: usize) -> bool {
+        self.taunts[nerd].is_none() && self.winner().is_none()
+    }
+
+    // Says one of a nerd's taunts if they can still taunt this turn; returns whether they did
+    pub fn taunt(&mut self, nerd: usize, taunt: usize) -> bool {
+        if !self.can_taunt(nerd) || taunt >= self.nerds[nerd].taunts.len() {
+            return false;
+        }
+        self.taunts[nerd] = Some(taunt);
+        let nerd = self.nerds[nerd];
+        self.add_message(&format!("{}: \"{}\"", nerd.name, nerd.taunts[taunt]));
+        true
+    }
+
+    // Returns what a nerd taunted this turn (if they did)
+    pub fn taunt_said(&self, nerd: usize) -> Option<&'static str> {
+        self.taunts[nerd].map(|taunt| self.nerds[nerd].taunts[taunt])
+    }
+
+    // Returns the index of the nerd whose turn it isn't
+    pub fn other_nerd(&self) -> usize {
+        usize::from(self.current_nerd == 0)
+    }
+
+    // Chooses the action of the current nerd and creates its equation; returns whether there's one to answer
+    // (if the action's script doesn't work, nothing happens and the turn passes)
+    pub fn choose_action(&mut self, action: usize) -> bool {
+        self.action_selected = action;
+        let equation = match self.nerds[self.current_nerd].equation(
+            action,
+            &self.nerds[self.other_nerd()],
+            self.critical,
+        ) {
+            Ok(equation) => equation,
+            Err(err) => {
+                let message = format!(
+                    "{}'s script didn't work, so nothing happened ({err})",
+                    self.nerds[self.current_nerd].actions[action].base_name()
+                );
+                self.add_message(&message);
+                self.current_nerd = self.other_nerd();
+                self.next_turn();
+                return false;
+            }
+        };
+        let level = self.difficulties[self.current_nerd].level();
+        self.result = equation.answer();
+        let missing = self
+            .missing_numbers
+            .then(|| equation.missing(level))
+            .flatten();
+        self.missing = missing.is_some();
+        let choices = match missing {
+            Some((text, hidden)) => {
+                self.equation = text;
+                self.answer = hidden;
+                Equation::missing_choices(hidden)
+            }
+            None => {
+                self.equation = equation.text(level);
+                self.answer = self.result;
+                equation.choices(level)
+            }
+        };
+        self.choices = if self.multiple_choice {
+            choices
+        } else {
+            Vec::new()
+        };
+        true
+    }
+
+    // Returns the equation of the chosen action
+    pub fn equation(&self) -> &str {
+        &self.equation
+    }
+
+    // Returns whether the equation has a missing number to find (with its result given) instead of a result
+    pub fn missing(&self) -> bool {
+        self.missing
+    }
+
+    // Returns the answers to choose from (none unless answers are multiple choice)
+    pub fn choices(&self) -> Vec<i32> {
+        self.choices.iter().map(|&(choice, _)| choice).collect()
+    }
+
+    // Returns the mistake that a wrong answer comes from (none if it's right)
+    pub fn mistake(&self, num: i32) -> Option<Mistake> {
+        if num == self.answer {
+            return None;
+        }
+        let mistake = self
+            .choices
+            .iter()
+            .find(|&&(choice, _)| choice == num)
+            .and_then(|&(_, mistake)| mistake);
+        Some(mistake.unwrap_or(Mistake::Miscalculation))
+    }
+
+    // Returns whether the current nerd's action will be a critical hit
+    pub fn critical(&self) -> bool {
+        self.critical
+    }
+
+    // Returns the answer to the equation of the chosen action (the missing number if there is one)
+    pub fn answer(&self) -> i32 {
+        self.answer
+    }
+
+    // Uses the chosen action if the answer is right and passes the turn; returns whether it was right
+    pub fn submit(&mut self, num: i32) -> bool {
+        let right = num == self.answer;
+        let name = self.nerds[self.current_nerd].name;
+        if right {
+            let (first, second) = self.nerds.split_at_mut(1);
+            let messages = if self.current_nerd == 0 {
+                first[0].use_action(
+                    self.action_selected,
+                    self.result,
+                    self.critical,
+                    &mut second[0],
+                )
+            } else {
+                second[0].use_action(
+                    self.action_selected,
+                    self.result,
+                    self.critical,
+                    &mut first[0],
+                )
+            };
+            for message in messages {
+                self.add_message(&message);
+            }
+        } else {
+            let message = self.nerds[self.current_nerd].failed_message(
+                self.action_selected,
+                self.result,
+                &self.nerds[self.other_nerd()],
+            );
+            self.add_message(&message);
+        }
+
+        let difficulty = &mut self.difficulties[self.current_nerd];
+        let level = difficulty.level();
+        if self.adaptive {
+            difficulty.update(right);
+        }
+        if difficulty.level() != level {
+            let message = format!(
+                "{name}'s equations are now at level {} ({})",
+                difficulty.level(),
+                difficulty.name()
+            );
+            self.add_message(&message);
+        }
+
+        // Only a right answer can earn another turn
+        if right && self.extra_turns && self.extra_turn() {
+            self.add_message(&format!("{name} is fast enough to go again!"));
+        } else {
+            self.current_nerd = self.other_nerd();
+        }
+        self.next_turn();
+        right
+    }
+
+    // Starts the turn of the current nerd
+    fn next_turn(&mut self) {
+        self.critical = self.nerds[self.current_nerd].roll_critical();
+        self.taunts = [None; 2];
+    }
+
+    // Returns whether the current nerd gets another turn for being faster than the other nerd
+    fn extra_turn(&self) -> bool {
+        let speed = self.nerds[self.current_nerd].speed - self.nerds[self.other_nerd()].speed;
+        speed > 0 && fastrand::i32(0..100) < (speed * EXTRA_TURN_CHANCE).min(MAX_EXTRA_TURN_CHANCE)
+    }
+
+    // Returns the index of the winning nerd, otherwise none
+    pub fn winner(&self) -> Option<usize> {
+        if self.nerds[0].health < 1 {
+            Some(1)
+        } else if self.nerds[1].health < 1 {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nerds::NERDS;
+
+    // Returns a duel between the first two roster nerds with every option turned on, partway through
+    fn duel_in_progress() -> Duel {
+        let mut duel = Duel::new([NERDS[0], NERDS[1]], 0);
+        duel.adaptive = true;
+        duel.extra_turns = true;
+        duel.multiple_choice = true;
+        duel.missing_numbers = true;
+        for turn in 0..5 {
+            duel.taunt(duel.current_nerd, 0);
+            assert!(duel.choose_action(turn % 2));
+            duel.submit(duel.answer() + turn as i32 % 2);
+        }
+        assert!(duel.choose_action(2));
+        duel
+    }
+
+    // Returns the save text of every nerd
+    fn nerd_texts(nerds: &Nerds) -> Vec<String> {
+        nerds.iter().map(Nerd::save_text).collect()
+    }
+
+    #[test]
+    fn loads_a_saved_duel_the_same() {
+        let duel = duel_in_progress();
+        let mut save = Save::new();
+        duel.save(&mut save);
+        let loaded = Duel::load(&save).expect("the duel was saved");
+
+        assert_eq!(nerd_texts(&loaded.nerds), nerd_texts(&duel.nerds));
+        assert_eq!(
+            nerd_texts(&loaded.starting_nerds),
+            nerd_texts(&duel.starting_nerds)
+        );
+        assert_eq!(loaded.current_nerd, duel.current_nerd);
+        assert_eq!(
+            loaded.difficulties.map(|difficulty| difficulty.save_text()),
+            duel.difficulties.map(|difficulty| difficulty.save_text())
+        );
+        assert_eq!(loaded.adaptive, duel.adaptive);
+        assert_eq!(loaded.extra_turns, duel.extra_turns);
+        assert_eq!(loaded.multiple_choice, duel.multiple_choice);
+        assert_eq!(loaded.missing_numbers, duel.missing_numbers);
+        assert_eq!(loaded.messages, duel.messages);
+        assert_eq!(loaded.action_selected, duel.action_selected);
+        assert_eq!(loaded.equation, duel.equation);
+        assert_eq!(loaded.answer, duel.answer);
+        assert_eq!(loaded.result, duel.result);
+        assert_eq!(loaded.missing, duel.missing);
+        assert!(loaded.choices == duel.choices);
+        assert_eq!(loaded.critical, duel.critical);
+        // Taunts start over when a duel is continued
+        assert_eq!(loaded.taunts, [None; 2]);
+    }
+
+    #[test]
+    fn loads_an_older_save_with_defaults() {
+        let (first, second) = (NERDS[0], NERDS[1]);
+        let mut save = Save::new();
+        save.set("nerd", format!("{}|5|9|1,2,3,4", first.name));
+        save.set("nerd", format!("{}|7|10|4,3,2,1", second.name));
+        save.set("difficulty", "2 1");
+        save.set("difficulty", "0 0");
+        save.set("current_nerd", 1);
+        save.set("adaptive", false);
+        save.set("action_selected", 3);
+        save.set("equation", "7 - 2 * 3");
+        save.set("answer", 1);
+        save.set("critical", true);
+        save.set("message", "The duel began");
+        let duel = Duel::load(&save).expect("older saves still load");
+
+        assert_eq!((duel.nerds[0].health, duel.nerds[1].health), (5, 7));
+        assert_eq!(duel.nerds[0].max_health, first.max_health);
+        assert_eq!(
+            duel.nerds[1].actions.map(|action| action.value()),
+            [4, 3, 2, 1]
+        );
+        assert_eq!(nerd_texts(&duel.starting_nerds()), nerd_texts(&duel.nerds));
+        assert_eq!(duel.current_nerd, 1);
+        assert_eq!(
+            duel.difficulties.map(|difficulty| difficulty.level()),
+            [2, 0]
+        );
+        assert!(!duel.adaptive);
+        assert!(!duel.extra_turns);
+        assert!(!duel.multiple_choice);
+        assert!(!duel.missing_numbers);
+        assert_eq!(duel.action_selected, 3);
+        assert_eq!(
+            (duel.equation(), duel.answer(), duel.result),
+            ("7 - 2 * 3", 1, 1)
+        );
+        assert!(!duel.missing());
+        assert!(duel.choices().is_empty());
+        assert!(duel.critical());
+        assert_eq!(duel.messages(), ["The duel began"]);
+    }
+}
@@ -0,0 +1,529 @@
+use std::fmt;
+
+// Names of the difficulty levels equations can be written at
+const LEVEL_NAMES: [&str; 5] = [
+    "Simple",
+    "Normal",
+    "Negatives",
+    "Extra Terms",
+    "Big Numbers",
+];
+
+// Levels where equations start getting written in harder ways
+const START_LEVEL: usize = 1;
+const NEGATIVE_LEVEL: usize = 2;
+const EXTRA_TERM_LEVEL: usize = 3;
+const BIG_NUMBER_LEVEL: usize = 4;
+
+// Right answers in a row needed to go up a level
+const LEVEL_UP_STREAK: usize = 3;
+
+// Biggest number added to operands when they're written as differences
+const MAX_EXTRA: i32 = 20;
+
+// Written in place of the number that's hidden when solving for a missing number
+const HIDDEN: &str = "?";
+
+// How many answers are shown in multiple choice (the right one and the wrong ones)
+pub const CHOICES: usize = 4;
+
+// Mistakes that wrong answer choices come from (in the order they're saved and shown)
+pub const MISTAKES: [Mistake; 4] = [
+    Mistake::SignError,
+    Mistake::OrderOfOperations,
+    Mistake::DroppedCritical,
+    Mistake::Miscalculation,
+];
+
+// A starting number that a product of factors is added to or subtracted from (the answer can be kept in bounds),
+// or an equation that was already written (by a script)
+#[derive(Clone)]
+pub struct Equation {
+    base: i32,
+    subtract: bool,
+    factors: Vec<i32>,
+    bounds: Option<(i32, i32)>,
+    critical: Option<usize>,
+    written: Option<(String, i32)>,
+}
+
+impl Equation {
+    // Creates a new equation
+    pub fn new(base: i32, subtract: bool, factors: Vec<i32>) -> Self {
+        Self {
+            base,
+            subtract,
+            factors,
+            bounds: None,
+            critical: None,
+            written: None,
+        }
+    }
+
+    // Creates an equation that's already written with its answer (it's the same at every level)
+    pub fn written(text: String, answer: i32) -> Self {
+        Self {
+            written: Some((text, answer)),
+            ..Self::new(answer, false, vec![0])
+        }
+    }
+
+    // Returns the equation with its answer kept between a minimum and a maximum
+    pub fn clamped(mut self, min: i32, max: i32) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    // Returns the equation with one of its factors marked as the critical hit multiplier
+    pub fn with_critical(mut self, factor: usize) -> Self {
+        self.critical = Some(factor);
+        self
+    }
+
+    // Returns the answer to the equation
+    pub fn answer(&self) -> i32 {
+        if let Some((_, answer)) = self.written {
+            return answer;
+        }
+        self.bounded(self.unclamped_answer())
+    }
+
+    // Returns the answer before it's kept in bounds (big enough that it can't overflow)
+    fn unclamped_answer(&self) -> i64 {
+        let product = self.factors.iter().fold(1i64, |product, &factor| {
+            product.saturating_mul(factor.into())
+        });
+        if self.subtract {
+            i64::from(self.base).saturating_sub(product)
+        } else {
+            i64::from(self.base).saturating_add(product)
+        }
+    }
+
+    // Returns a number kept in the bounds of the equation
+    fn bounded(&self, num: i64) -> i32 {
+        let (min, max) = self.bounds.unwrap_or((i32::MIN, i32::MAX));
+        num.clamp(min.into(), max.into()) as i32
+    }
+
+    // Returns the answers shown in multiple choice for a difficulty level in a random order, with the
+    // mistake each wrong one comes from (wrong answers that no mistake gives are made up by being a bit off)
+    pub fn choices(&self, level: usize) -> Vec<(i32, Option<Mistake>)> {
+        let answer = self.answer();
+        let mut choices = vec![(answer, None)];
+        if self.written.is_none() {
+            let mut flipped = self.clone();
+            flipped.subtract = !flipped.subtract;
+            let mut mistakes = vec![
+                (Mistake::SignError, flipped.unclamped_answer()),
+                (Mistake::OrderOfOperations, self.left_to_right(level)),
+            ];
+            if let Some(factor) = self.critical {
+                let mut dropped = self.clone();
+                dropped.factors.remove(factor);
+                mistakes.push((Mistake::DroppedCritical, dropped.unclamped_answer()));
+            }
+            for (mistake, num) in mistakes {
+                let num = self.mistaken(num);
+                if choices.iter().all(|&(choice, _)| choice != num) {
+                    choices.push((num, Some(mistake)));
+                }
+            }
+        }
+        fill_choices(answer, choices)
+    }
+
+    // Returns the numbers shown in multiple choice for a hidden number in a random order, with the mistake
+    // each wrong one comes from (its opposite is a sign error)
+    pub fn missing_choices(hidden: i32) -> Vec<(i32, Option<Mistake>)> {
+        let mut choices = vec![(hidden, None)];
+        if hidden != 0 {
+            choices.push((hidden.saturating_neg(), Some(Mistake::SignError)));
+        }
+        fill_choices(hidden, choices)
+    }
+
+    // Returns the answer worked out from left to right as it's written, ignoring the order of operations
+    fn left_to_right(&self, level: usize) -> i64 {
+        let factors = self.written_factors(level);
+        let (first, rest) = factors.split_first().unwrap_or((&1, &[]));
+        let start = if self.subtract {
+            i64::from(self.base).saturating_sub((*first).into())
+        } else {
+            i64::from(self.base).saturating_add((*first).into())
+        };
+        rest.iter()
+            .fold(start, |num, &factor| num.saturating_mul(factor.into()))
+    }
+
+    // Returns a wrong answer the way it would be worked out (only kept in bounds if min or max is written)
+    fn mistaken(&self, num: i64) -> i32 {
+        if i64::from(self.answer()) == self.unclamped_answer() {
+            num.clamp(i32::MIN.into(), i32::MAX.into()) as i32
+        } else {
+            self.bounded(num)
+        }
+    }
+
+    // Returns the factors written for a difficulty level (the easiest level leaves out factors of 1)
+    fn written_factors(&self, level: usize) -> Vec<i32> {
+        let mut factors = self.factors.clone();
+        if level < START_LEVEL && factors.len() > 1 {
+            factors.retain(|&factor| factor != 1);
+            if factors.is_empty() {
+                factors.push(1);
+            }
+        }
+        factors
+    }
+
+    // Returns the equation written for a difficulty level (the answer is the same at every level)
+    pub fn text(&self, level: usize) -> String {
+        if let Some((text, _)) = &self.written {
+            return text.clone();
+        }
+        let (text, _) = self.write(level, false);
+        match self.bounds {
+            Some((min, _)) if self.unclamped_answer() < min.into() => format!("max({min}, {text})"),
+            Some((_, max)) if self.unclamped_answer() > max.into() => format!("min({max}, {text})"),
+            _ => text,
+        }
+    }
+
+    // Returns the equation written for a difficulty level with one of its numbers hidden and the answer
+    // given, and the hidden number (none if it can't be worked out, like when the answer is kept in bounds)
+    pub fn missing(&self, level: usize) -> Option<(String, i32)> {
+        if self.written.is_some() || i64::from(self.answer()) != self.unclamped_answer() {
+            return None;
+        }
+        let (text, hidden) = self.write(level, true);
+        Some((format!("{text} = {}", self.answer()), hidden?))
+    }
+
+    // Writes the equation for a difficulty level, hiding a random number as ? if asked to;
+    // returns the text and the hidden number
+    fn write(&self, level: usize, hide: bool) -> (String, Option<i32>) {
+        let mut factors = self.written_factors(level);
+        let mut subtract = self.subtract;
+
+        let negated = fastrand::usize(0..factors.len());
+        if level >= NEGATIVE_LEVEL {
+            factors[negated] = -factors[negated];
+            subtract = !subtract;
+        }
+
+        // The base is 0 and the factors come after it; a factor can only be hidden if the others
+        // don't multiply to 0 (otherwise any number would work)
+        let hideable: Vec<usize> = (0..=factors.len())
+            .filter(|&i| {
+                i == 0
+                    || factors
+                        .iter()
+                        .enumerate()
+                        .all(|(j, &factor)| j == i - 1 || factor != 0)
+            })
+            .collect();
+        let hidden = hide.then(|| hideable[fastrand::usize(0..hideable.len())]);
+
+        let bigger = (level >= BIG_NUMBER_LEVEL).then(|| fastrand::usize(0..factors.len()));
+        let factor_texts: Vec<String> = factors
+            .iter()
+            .enumerate()
+            .map(|(i, &factor)| {
+                if Some(i + 1) == hidden {
+                    HIDDEN.to_string()
+                } else if Some(i) == bigger {
+                    let extra = fastrand::i32(1..=MAX_EXTRA);
+                    format!("({} - {})", operand(factor + extra), extra)
+                } else {
+                    operand(factor)
+                }
+            })
+            .collect();
+
+        let base = if hidden == Some(0) {
+            HIDDEN.to_string()
+        } else if level >= EXTRA_TERM_LEVEL {
+            let extra = fastrand::i32(1..=MAX_EXTRA.max(self.base.abs() / 2));
+            format!("{} + {}", self.base - extra, extra)
+        } else {
+            self.base.to_string()
+        };
+        let text = format!(
+            "{} {} {}",
+            base,
+            if subtract { "-" } else { "+" },
+            factor_texts.join(" * ")
+        );
+        let hidden = hidden.map(|i| if i == 0 { self.base } else { factors[i - 1] });
+        (text, hidden)
+    }
+}
+
+impl fmt::Display for Equation {
+    // Writes the equation at the starting level
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text(START_LEVEL))
+    }
+}
+
+// Adds made up wrong answers (a bit off from the right one) until there are enough, and shuffles them
+fn fill_choices(
+    answer: i32,
+    mut choices: Vec<(i32, Option<Mistake>)>,
+) -> Vec<(i32, Option<Mistake>)> {
+    choices.truncate(CHOICES);
+    while choices.len() < CHOICES {
+        let off = fastrand::i32(1..=MAX_EXTRA) * if fastrand::bool() { 1 } else { -1 };
+        let num = answer.saturating_add(off);
+        if choices.iter().all(|&(choice, _)| choice != num) {
+            choices.push((num, Some(Mistake::Miscalculation)));
+        }
+    }
+    fastrand::shuffle(&mut choices);
+    choices
+}
+
+// Returns a number written as an operand (negative numbers get brackets)
+fn operand(num: i32) -> String {
+    if num < 0 {
+        format!("({num})")
+    } else {
+        num.to_string()
+    }
+}
+
+// Common mistakes that can be made while working out an equation
+#[derive(Copy, Clone, PartialEq)]
+pub enum Mistake {
+    SignError,
+    OrderOfOperations,
+    DroppedCritical,
+    Miscalculation,
+}
+
+impl Mistake {
+    // Returns a description of the mistake
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SignError => "Sign errors (adding instead of subtracting, or the other way)",
+            Self::OrderOfOperations => "Ignoring the order of operations",
+            Self::DroppedCritical => "Dropping the critical hit multiplier",
+            Self::Miscalculation => "Other miscalculations",
+        }
+    }
+
+    // Returns the name of the mistake written for a save file
+    pub fn save_name(&self) -> &'static str {
+        match self {
+            Self::SignError => "sign_error",
+            Self::OrderOfOperations => "order_of_operations",
+            Self::DroppedCritical => "dropped_critical",
+            Self::Miscalculation => "miscalculation",
+        }
+    }
+}
+
+// How hard a player's equations are written, changing with how well they answer
+#[derive(Copy, Clone)]
+pub struct Difficulty {
+    level: usize,
+    streak: usize,
+}
+
+impl Difficulty {
+    // Creates a difficulty at the starting level
+    pub const fn new() -> Self {
+        Self {
+            level: START_LEVEL,
+            streak: 0,
+        }
+    }
+
+    // Returns the level and streak written for a save file
+    pub fn save_text(&self) -> String {
+        format!("{} {}", self.level, self.streak)
+    }
+
+    // Returns a difficulty read from a save file
+    pub fn from_save(text: &str) -> Option<Self> {
+        let (level, streak) = text.split_once(' ')?;
+        Some(Self {
+            level: level.parse::<usize>().ok()?.min(LEVEL_NAMES.len() - 1),
+            streak: streak.parse().ok()?,
+        })
+    }
+
+    // Returns the current level
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    // Returns the name of the current level
+    pub fn name(&self) -> &'static str {
+        LEVEL_NAMES[self.level]
+    }
+
+    // Goes up a level after enough right answers in a row, and down a level after a wrong answer
+    pub fn update(&mut self, right: bool) {
+        if right {
+            self.streak += 1;
+            if self.streak >= LEVEL_UP_STREAK {
+                self.level = (self.level + 1).min(LEVEL_NAMES.len() - 1);
+                self.streak = 0;
+            }
+        } else {
+            self.level = self.level.saturating_sub(1);
+            self.streak = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nerds::NERDS;
+    use crate::script::Script;
+
+    // Equations of every kind nerds make: damage, healing, weakening and strengthening, with factors of 1 and 0,
+    // critical hits, and answers kept in bounds
+    fn equations() -> Vec<Equation> {
+        vec![
+            Equation::new(200, true, vec![3, 10, 1]),
+            Equation::new(50, false, vec![2, 7, 1]).clamped(0, 60),
+            Equation::new(120, true, vec![4, 9, 2, 2])
+                .clamped(0, 400)
+                .with_critical(2),
+            Equation::new(-8, true, vec![1, 1]),
+            Equation::new(10, false, vec![0, 5]),
+            Equation::new(3, true, vec![2, 3]).clamped(1, 20),
+        ]
+    }
+
+    // Works out the number of written math (the same way scripts do)
+    fn solve(text: &str) -> i32 {
+        let nerd = NERDS[0];
+        Script::parse(&format!("equation {text}"))
+            .and_then(|script| script.equation(&nerd, &nerd, 0, false))
+            .unwrap_or_else(|err| panic!("{text} can't be worked out: {err}"))
+            .1
+    }
+
+    #[test]
+    fn has_the_same_answer_at_every_level() {
+        for equation in equations() {
+            for level in 0..LEVEL_NAMES.len() {
+                for _ in 0..50 {
+                    let text = equation.text(level);
+                    assert_eq!(solve(&text), equation.answer(), "{text}");
+                }
+            }
+        }
+        let written = Equation::written("2 + 2".to_string(), 4);
+        assert_eq!(written.text(BIG_NUMBER_LEVEL), "2 + 2");
+        assert_eq!(written.answer(), 4);
+    }
+
+    #[test]
+    fn goes_up_after_a_streak_and_down_after_a_wrong_answer() {
+        let mut difficulty = Difficulty::new();
+        assert_eq!(difficulty.level(), START_LEVEL);
+        for _ in 1..LEVEL_UP_STREAK {
+            difficulty.update(true);
+        }
+        assert_eq!(difficulty.level(), START_LEVEL);
+        difficulty.update(true);
+        assert_eq!(difficulty.level(), START_LEVEL + 1);
+
+        // A wrong answer also starts the streak over
+        difficulty.update(true);
+        difficulty.update(false);
+        assert_eq!(difficulty.level(), START_LEVEL);
+        for _ in 1..LEVEL_UP_STREAK {
+            difficulty.update(true);
+        }
+        assert_eq!(difficulty.level(), START_LEVEL);
+
+        for _ in 0..LEVEL_NAMES.len() * LEVEL_UP_STREAK {
+            difficulty.update(true);
+        }
+        assert_eq!(difficulty.level(), LEVEL_NAMES.len() - 1);
+        for _ in 0..LEVEL_NAMES.len() + 1 {
+            difficulty.update(false);
+        }
+        assert_eq!(difficulty.level(), 0);
+    }
+
+    #[test]
+    fn gives_each_wrong_choice_the_mistake_it_comes_from() {
+        let equation = Equation::new(100, true, vec![3, 4, 2]).with_critical(2);
+        let mut choices = equation.choices(START_LEVEL);
+        choices.sort_by_key(|&(choice, _)| choice);
+        assert!(
+            choices
+                == [
+                    (76, None),
+                    (88, Some(Mistake::DroppedCritical)),
+                    (124, Some(Mistake::SignError)),
+                    (776, Some(Mistake::OrderOfOperations)),
+                ]
+        );
+    }
+
+    #[test]
+    fn never_gives_the_answer_as_a_wrong_choice() {
+        let equations = equations()
+            .into_iter()
+            .chain([Equation::written("6 * 7".to_string(), 42)]);
+        for equation in equations {
+            for level in 0..LEVEL_NAMES.len() {
+                for _ in 0..50 {
+                    let choices = equation.choices(level);
+                    assert_eq!(choices.len(), CHOICES);
+                    for (i, &(choice, mistake)) in choices.iter().enumerate() {
+                        assert_eq!(mistake.is_none(), choice == equation.answer());
+                        assert!(choices[..i].iter().all(|&(other, _)| other != choice));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hides_a_number_that_solves_the_equation() {
+        for equation in equations() {
+            for level in 0..LEVEL_NAMES.len() {
+                for _ in 0..50 {
+                    let Some((text, hidden)) = equation.missing(level) else {
+                        assert_ne!(i64::from(equation.answer()), equation.unclamped_answer());
+                        continue;
+                    };
+                    let (left, right) = text.split_once(" = ").expect("the answer is given");
+                    assert_eq!(right.parse(), Ok(equation.answer()));
+                    assert_eq!(left.matches(HIDDEN).count(), 1, "{text}");
+                    let solved = left.replace(HIDDEN, &operand(hidden));
+                    assert_eq!(solve(&solved), equation.answer(), "{text} ({hidden})");
+                }
+            }
+        }
+        assert!(Equation::written("1 + 1".to_string(), 2)
+            .missing(START_LEVEL)
+            .is_none());
+    }
+
+    #[test]
+    fn gives_the_opposite_of_a_hidden_number_as_a_sign_error() {
+        for hidden in [-7, 0, 1, 12, i32::MAX, i32::MIN] {
+            let choices = Equation::missing_choices(hidden);
+            assert_eq!(choices.len(), CHOICES);
+            for (i, &(choice, mistake)) in choices.iter().enumerate() {
+                assert_eq!(mistake.is_none(), choice == hidden);
+                assert!(choices[..i].iter().all(|&(other, _)| other != choice));
+            }
+            assert_eq!(
+                choices.contains(&(hidden.saturating_neg(), Some(Mistake::SignError))),
+                hidden != 0
+            );
+        }
+    }
+}
@@ -1,47 +1,86 @@
-use crate::nerds::{Nerd, Nerds};
+use crate::ai::Ai;
+use crate::campaign::Campaign;
+use crate::creator::{self, Creator};
+use crate::duel::Duel;
+use crate::nerds::{Nerd, Nerds, NERDS};
+use crate::options::{Options, SelectionMode};
+use crate::practice::Practice;
+use crate::saved_match::SavedMatch;
+use crate::solver::Analysis;
+use crate::stats::Stats;
 use crate::tui::Tui;
+use crate::unlocks::{self, Unlockable, Unlocks};
 
 // String used at beginning of game to introduce players
-const GAME_START_MESSAGE: &str = "Two nerds bump into each other. nerd0 and nerd1 glare at each other. The fight chant is heard. The AC Nerd Duels have begun.";
-const GAME_END_MESSAGE: &str = "As the dust settles, nerd0 looks down at the unconscious nerd1 before being escorted to the principal's office.";
+pub const GAME_START_MESSAGE: &str = "Two nerds bump into each other. nerd0 and nerd1 glare at each other. The fight chant is heard. The AC Nerd Duels have begun.";
+pub const GAME_END_MESSAGE: &str = "As the dust settles, nerd0 looks down at the unconscious nerd1 before being escorted to the principal's office.";
+
+// Turns searched ahead when giving a hint
+pub const HINT_DEPTH: usize = 5;
+
+// Shown when a player chooses an action they haven't unlocked
+pub const LOCKED_ACTION_MESSAGE: &str = "That action is still locked";
 
 // Contains game information
 pub struct Game {
     tui: Tui,
     game_state: GameState,
-    nerds: Option<Nerds>,
-    current_nerd: usize,
-    action_selected: usize,
-    equation: String,
-    answer: i32,
-    critical: bool,
+    duel: Option<Duel>,
+    ais: [Option<Ai>; 2],
+    ai_timer: usize,
+    campaign: Option<Campaign>,
+    practice: Option<Practice>,
+    creator: Option<Creator>,
+    roster: Vec<Nerd>,
+    menu_options: Vec<MenuOption>,
+    pause_options: Vec<PauseOption>,
+    paused_state: Option<GameState>,
+    options: Options,
+    unlocks: Unlocks,
+    stats: Stats,
+    last_loser: Option<usize>,
+    quit: bool,
 }
 
 impl Game {
     // Creates new instance of the game
     pub fn new() -> Self {
+        let options = Options::load();
+        let mut tui = Tui::new();
+        tui.set_reveal_critical(options.reveal_critical);
         Self {
-            tui: Tui::new(),
+            tui,
             game_state: GameState::Intro,
-            nerds: None,
-            current_nerd: 0,
-            action_selected: 0,
-            equation: String::new(),
-            answer: 0,
-            critical: Nerd::critical(),
+            duel: None,
+            ais: [None, None],
+            ai_timer: 0,
+            campaign: None,
+            practice: None,
+            creator: None,
+            roster: creator::roster(),
+            menu_options: Vec::new(),
+            pause_options: Vec::new(),
+            paused_state: None,
+            options,
+            unlocks: Unlocks::load(),
+            stats: Stats::load(),
+            last_loser: None,
+            quit: false,
         }
     }
 
     // Runs every frame
     pub fn main_loop(&mut self) {
         loop {
-            self.tui.update(
-                self.game_state,
-                &self.nerds,
-                self.current_nerd,
-                &self.equation,
-            );
-            if self.tui.should_quit() {
+            let equation = match (&self.duel, &self.practice) {
+                (_, Some(practice)) => practice.equation(),
+                (Some(duel), None) => duel.equation(),
+                (None, None) => "",
+            };
+            self.tui
+                .update(self.game_state, self.duel.as_ref(), equation);
+            if self.tui.should_quit() || self.quit {
+                self.save_match();
                 break;
             }
             self.update();
@@ -53,114 +92,568 @@ impl Game {
         match self.game_state {
             GameState::Intro => {
                 if self.tui.intro_done() {
-                    self.game_state = GameState::MainMenu
+                    self.open_main_menu();
                 }
             }
             GameState::MainMenu => {
-                if let Some(nerds) = self.tui.nerds_chosen() {
-                    self.start_game(nerds);
+                if let Some(option) = self.tui.menu_chosen() {
+                    self.choose_menu_option(self.menu_options[option]);
+                }
+            }
+            GameState::NerdSelect(mode) => {
+                if self.tui.secret_entered() {
+                    self.enter_secret();
+                }
+                if self.tui.back() {
+                    self.open_main_menu();
+                } else if let Some(nerds) = self.tui.nerds_chosen() {
+                    match mode {
+                        GameMode::Versus => self.start_versus(nerds),
+                        GameMode::Campaign => {
+                            self.campaign = Some(Campaign::new(&nerds[0]));
+                            self.start_campaign_fight();
+                        }
+                    }
+                }
+            }
+            GameState::Dialogue(scene) => {
+                if self.tui.dialogue_done() {
+                    self.finish_scene(scene);
                 }
             }
+            GameState::InGame(_) | GameState::Practice(PracticeState::Solving)
+                if self.tui.pause_pressed() =>
+            {
+                self.pause()
+            }
             GameState::InGame(InGameState::Choosing) => self.update_choosing(),
             GameState::InGame(InGameState::Mathing) => self.update_mathing(),
-            GameState::GameEnd => (),
+            GameState::InGame(InGameState::Taunting) => self.update_taunting(),
+            GameState::Practice(PracticeState::Setup) => {
+                if self.tui.back() {
+                    self.open_main_menu();
+                } else if let Some(settings) = self.tui.practice_settings_chosen() {
+                    self.practice = Some(Practice::new(settings));
+                    self.game_state = GameState::Practice(PracticeState::Solving);
+                }
+            }
+            GameState::Practice(PracticeState::Solving) => self.update_practice(),
+            GameState::Practice(PracticeState::Summary) => {
+                if self.tui.confirmed() {
+                    self.open_main_menu();
+                }
+            }
+            GameState::GameEnd => {
+                if self.tui.confirmed() {
+                    if self.campaign.is_some() {
+                        self.finish_campaign_fight();
+                    } else {
+                        self.open_main_menu();
+                    }
+                }
+            }
+            GameState::Stats => {
+                if self.tui.confirmed() || self.tui.back() {
+                    self.open_main_menu();
+                }
+            }
+            GameState::Paused => self.update_paused(),
+            GameState::Options => self.update_options(),
+            GameState::Creator => self.update_creator(),
         }
     }
 
-    // Initializes the start of the game
-    fn start_game(&mut self, nerds: Nerds) {
-        self.game_state = GameState::InGame(InGameState::Choosing);
-        self.tui.add_action_message(
-            &GAME_START_MESSAGE
-                .replace("nerd0", nerds[0].name)
-                .replace("nerd1", nerds[1].name),
+    // Goes to the main menu with the options currently available
+    fn open_main_menu(&mut self) {
+        self.game_state = GameState::MainMenu;
+        self.duel = None;
+        self.campaign = None;
+        self.practice = None;
+        self.creator = None;
+        self.paused_state = None;
+        let mut options = Vec::new();
+        if SavedMatch::exists() {
+            options.push(MenuOption::ContinueMatch);
+        }
+        options.extend([MenuOption::Versus, MenuOption::NewCampaign]);
+        if Campaign::load().is_some() {
+            options.push(MenuOption::ContinueCampaign);
+        }
+        options.extend([
+            MenuOption::Practice,
+            MenuOption::CreateNerd,
+            MenuOption::Stats,
+            MenuOption::Options,
+        ]);
+        self.tui.open_menu(
+            options
+                .iter()
+                .map(|option| option.name().to_string())
+                .collect(),
         );
-        self.nerds = Some(nerds);
+        self.menu_options = options;
     }
 
-    // Updates the game when choosing action
-    fn update_choosing(&mut self) {
-        if let Some(nerd_names) = self.game_ended() {
-            self.end_game(nerd_names)
+    // Does what the chosen main menu option says
+    fn choose_menu_option(&mut self, option: MenuOption) {
+        match option {
+            MenuOption::ContinueMatch => self.continue_match(),
+            MenuOption::Versus => self.open_nerd_select(GameMode::Versus),
+            MenuOption::NewCampaign => self.open_nerd_select(GameMode::Campaign),
+            MenuOption::ContinueCampaign => {
+                self.campaign = Campaign::load();
+                self.start_campaign_fight();
+            }
+            MenuOption::Practice => self.game_state = GameState::Practice(PracticeState::Setup),
+            MenuOption::CreateNerd => self.open_creator(),
+            MenuOption::Stats => {
+                self.tui.set_info(self.stats.lines());
+                self.game_state = GameState::Stats;
+            }
+            MenuOption::Options => self.open_options(),
         }
-        if let Some(action) = self.tui.action_chosen() {
-            self.action_selected = action;
-            self.game_state = GameState::InGame(InGameState::Mathing);
-            let other = usize::from(self.current_nerd == 0);
-            if let Some(nerds) = &self.nerds {
-                (self.equation, self.answer) = nerds[self.current_nerd].equation(
-                    self.action_selected,
-                    &nerds[other],
-                    self.critical,
-                );
+    }
+
+    // Goes to nerd selection, using the selection options for versus matches
+    fn open_nerd_select(&mut self, mode: GameMode) {
+        self.game_state = GameState::NerdSelect(mode);
+        // Only roster nerds have campaigns, so created nerds can only be picked for versus
+        let nerds = match mode {
+            GameMode::Versus => self.roster.len(),
+            GameMode::Campaign => NERDS.len(),
+        };
+        let selectable = (0..nerds)
+            .filter(|&i| {
+                self.unlocks
+                    .is_unlocked(Unlockable::Nerd(self.roster[i].name))
+            })
+            .collect();
+        let roster = self.roster.clone();
+        let selection_mode = self.options.selection_mode;
+        match mode {
+            GameMode::Versus
+                if self
+                    .unlocks
+                    .is_unlocked(Unlockable::Mode(selection_mode.name())) =>
+            {
+                self.tui.start_nerd_select(
+                    roster,
+                    selectable,
+                    selection_mode,
+                    self.options.mirror_matches,
+                )
             }
+            _ => self
+                .tui
+                .start_nerd_select(roster, selectable, SelectionMode::Standard, true),
         }
     }
 
-    // Returns the winning nerd, otherwise none
-    fn game_ended(&self) -> Option<[&'static str; 2]> {
-        if let Some(nerds) = &self.nerds {
-            if nerds[0].health < 1 {
-                return Some([nerds[1].name, nerds[0].name]);
-            } else if nerds[1].health < 1 {
-                return Some([nerds[0].name, nerds[1].name]);
+    // Unlocks what the secret sequence unlocks and selects the nerds it unlocks
+    fn enter_secret(&mut self) {
+        for unlockable in self.unlocks.enter_sequence() {
+            if let Unlockable::Nerd(name) = unlockable {
+                if let Some(nerd) = Nerd::roster_index(name) {
+                    self.tui.select_nerd(nerd);
+                }
             }
         }
-        None
     }
 
-    // Ends the game
-    fn end_game(&mut self, nerd_names: [&str; 2]) {
-        self.game_state = GameState::GameEnd;
-        self.tui.add_action_message(
-            &GAME_END_MESSAGE
-                .replace("nerd0", nerd_names[0])
-                .replace("nerd1", nerd_names[1]),
+    // Pauses what is going on and shows the pause menu
+    fn pause(&mut self) {
+        if let Some(practice) = &mut self.practice {
+            practice.pause();
+        }
+        self.paused_state = Some(self.game_state);
+        self.open_pause_menu();
+    }
+
+    // Shows the pause menu
+    fn open_pause_menu(&mut self) {
+        self.game_state = GameState::Paused;
+        let mut options = vec![PauseOption::Resume, PauseOption::Restart];
+        if self.practice.is_none() {
+            options.push(PauseOption::SaveMatch);
+        }
+        options.extend([
+            PauseOption::MainMenu,
+            PauseOption::Options,
+            PauseOption::Quit,
+        ]);
+        self.tui.open_menu(
+            options
+                .iter()
+                .map(|option| option.name().to_string())
+                .collect(),
         );
+        self.pause_options = options;
     }
 
-    // Updates the game when entering math answer
-    fn update_mathing(&mut self) {
-        if self.tui.back() {
+    // Goes back to what was going on before pausing
+    fn resume(&mut self) {
+        if let Some(practice) = &mut self.practice {
+            practice.resume();
+        }
+        if let Some(state) = self.paused_state.take() {
+            self.game_state = state;
+        }
+    }
+
+    // Updates the game when paused
+    fn update_paused(&mut self) {
+        if self.tui.pause_pressed() || self.tui.back() {
+            self.resume();
+            return;
+        }
+        let Some(option) = self.tui.menu_chosen() else {
+            return;
+        };
+        match self.pause_options[option] {
+            PauseOption::Resume => self.resume(),
+            PauseOption::Restart => self.restart(),
+            PauseOption::SaveMatch => {
+                self.save_match();
+                if let Some(duel) = &mut self.duel {
+                    duel.add_message("Match saved");
+                }
+            }
+            PauseOption::MainMenu => self.open_main_menu(),
+            PauseOption::Options => self.open_options(),
+            PauseOption::Quit => self.quit = true,
+        }
+    }
+
+    // Saves the match in progress (if there is one) so it can be continued later
+    fn save_match(&self) {
+        let state = self.paused_state.unwrap_or(self.game_state);
+        if let (Some(duel), GameState::InGame(in_game_state)) = (&self.duel, state) {
+            SavedMatch::save(
+                duel,
+                self.ais,
+                matches!(in_game_state, InGameState::Mathing),
+                self.campaign.is_some(),
+            );
+        }
+    }
+
+    // Continues the saved match exactly where it was left off
+    fn continue_match(&mut self) {
+        let Some(saved) = SavedMatch::load() else {
+            return;
+        };
+        fastrand::seed(saved.seed);
+        if saved.campaign {
+            self.campaign = Campaign::load();
+        }
+        self.tui
+            .set_ais([saved.ais[0].is_some(), saved.ais[1].is_some()]);
+        self.tui.set_locked_actions(self.unlocks.locked_actions());
+        self.duel = Some(saved.duel);
+        self.ais = saved.ais;
+        self.ai_timer = 0;
+        self.game_state = GameState::InGame(if saved.mathing {
+            InGameState::Mathing
+        } else {
+            InGameState::Choosing
+        });
+    }
+
+    // Starts the paused duel or practice session over
+    fn restart(&mut self) {
+        self.paused_state = None;
+        if let Some(practice) = &self.practice {
+            self.practice = Some(Practice::new(practice.settings()));
+            self.game_state = GameState::Practice(PracticeState::Solving);
+        } else if let Some(duel) = &self.duel {
+            self.start_duel(duel.starting_nerds(), self.ais);
             self.game_state = GameState::InGame(InGameState::Choosing);
+        }
+    }
+
+    // Shows the options menu
+    fn open_options(&mut self) {
+        self.game_state = GameState::Options;
+        self.tui.open_menu(self.options.lines());
+    }
+
+    // Updates the game when changing options
+    fn update_options(&mut self) {
+        if self.tui.back() {
+            if self.paused_state.is_some() {
+                self.open_pause_menu();
+            } else {
+                self.open_main_menu();
+            }
+        } else if let Some((option, forward)) = self.tui.menu_changed() {
+            self.options.change(option, forward, &self.unlocks);
+            self.options.save();
+            if let Some(duel) = &mut self.duel {
+                duel.adaptive = self.options.adaptive_difficulty;
+                duel.extra_turns = self.options.extra_turns;
+                duel.multiple_choice = self.options.multiple_choice;
+                duel.missing_numbers = self.options.missing_numbers;
+            }
+            self.tui.set_reveal_critical(self.options.reveal_critical);
+            self.tui.set_menu_items(self.options.lines());
+        }
+    }
+
+    // Shows the nerd creator with a new nerd
+    fn open_creator(&mut self) {
+        let creator = Creator::new();
+        self.tui.open_menu(creator.lines());
+        self.tui.set_info(creator.info());
+        self.tui.set_preview(creator.sprite());
+        self.creator = Some(creator);
+        self.game_state = GameState::Creator;
+    }
+
+    // Updates the game when making a nerd in the creator
+    fn update_creator(&mut self) {
+        let Some(creator) = &mut self.creator else {
+            return;
+        };
+        if self.tui.back() {
+            self.open_main_menu();
             return;
         }
-        if let Some(nerds) = &mut self.nerds {
-            let other = usize::from(self.current_nerd == 0);
-            if let Some(num) = self.tui.math_chosen() {
-                if num == self.answer {
-                    let (first, second) = nerds.split_at_mut(1);
-                    self.tui.add_action_message(&if self.current_nerd == 0 {
-                        first[0].use_action(
-                            self.action_selected,
-                            self.answer,
-                            self.critical,
-                            &mut second[0],
-                        )
+        if let Some((item, forward)) = self.tui.menu_changed() {
+            creator.change(item, forward);
+        }
+        if let Some((item, text)) = self.tui.text_typed() {
+            creator.type_text(item, &text);
+        }
+        if let Some(item) = self.tui.erase_pressed() {
+            creator.erase(item);
+        }
+        if let Some(item) = self.tui.menu_chosen() {
+            if let Some(nerd) = creator.enter(item, &self.roster) {
+                self.roster.push(nerd);
+            }
+        }
+        self.tui.set_menu_items(creator.lines());
+        self.tui.set_info(creator.info());
+        self.tui.set_preview(creator.sprite());
+    }
+
+    // Initializes a match between two players
+    fn start_versus(&mut self, nerds: Nerds) {
+        self.start_duel(nerds, [None, None]);
+        self.game_state = GameState::InGame(InGameState::Choosing);
+    }
+
+    // Initializes the start of a duel
+    fn start_duel(&mut self, nerds: Nerds, ais: [Option<Ai>; 2]) {
+        self.tui.set_ais([ais[0].is_some(), ais[1].is_some()]);
+        self.tui.set_locked_actions(self.unlocks.locked_actions());
+        self.duel = Some(Duel::start(nerds, &self.options, self.last_loser));
+        self.ais = ais;
+        self.ai_timer = 0;
+    }
+
+    // Sets up the current campaign fight and shows its intro
+    fn start_campaign_fight(&mut self) {
+        if let Some(campaign) = &self.campaign {
+            campaign.save();
+            let (nerds, ai, intro) = (campaign.nerds(), campaign.ai(), campaign.intro());
+            let (fight, fights) = campaign.progress();
+            self.start_duel(nerds, [None, Some(ai)]);
+            if let Some(duel) = &mut self.duel {
+                duel.add_message(&format!("Fight {fight} of {fights}"));
+            }
+            self.start_scene(CampaignScene::Intro, intro);
+        }
+    }
+
+    // Shows lines of dialogue
+    fn start_scene(&mut self, scene: CampaignScene, lines: Vec<String>) {
+        self.tui.start_dialogue(lines);
+        self.game_state = GameState::Dialogue(scene);
+    }
+
+    // Moves on after a campaign fight is over
+    fn finish_campaign_fight(&mut self) {
+        let won = self.duel.as_ref().and_then(Duel::winner) == Some(0);
+        if let Some(campaign) = &self.campaign {
+            let (scene, lines) = if won {
+                (CampaignScene::Outro, campaign.outro())
+            } else {
+                (CampaignScene::Defeat, campaign.defeat())
+            };
+            self.start_scene(scene, lines);
+        }
+    }
+
+    // Moves on after dialogue is over
+    fn finish_scene(&mut self, scene: CampaignScene) {
+        match scene {
+            CampaignScene::Intro => self.game_state = GameState::InGame(InGameState::Choosing),
+            CampaignScene::Outro => {
+                if let Some(campaign) = &mut self.campaign {
+                    if campaign.advance() {
+                        Campaign::delete();
+                        let mut ending = campaign.ending();
+                        ending.extend(Self::unlock_lines(self.unlocks.complete_campaign()));
+                        self.start_scene(CampaignScene::Ending, ending);
                     } else {
-                        second[0].use_action(
-                            self.action_selected,
-                            self.answer,
-                            self.critical,
-                            &mut first[0],
-                        )
-                    });
+                        self.start_campaign_fight();
+                    }
+                }
+            }
+            CampaignScene::Defeat => self.start_campaign_fight(),
+            CampaignScene::Ending => self.open_main_menu(),
+        }
+    }
+
+    // Updates the game when choosing action
+    fn update_choosing(&mut self) {
+        let Some(duel) = &mut self.duel else {
+            return;
+        };
+        if let Some(winner) = duel.winner() {
+            let names = [duel.nerds[winner].name, duel.nerds[1 - winner].name];
+            duel.add_message(&fill_names(GAME_END_MESSAGE, names));
+            self.last_loser = Some(1 - winner);
+            if self.ais[winner].is_none() {
+                let mut unlocked = self.unlocks.add_win();
+                if duel.nerds[winner].health >= duel.starting_nerds()[winner].health {
+                    unlocked.extend(self.unlocks.achieve(unlocks::FLAWLESS));
+                }
+                for line in Self::unlock_lines(unlocked) {
+                    duel.add_message(&line);
                 }
-                self.game_state = GameState::InGame(InGameState::Choosing);
-                self.current_nerd = other;
-                self.critical = Nerd::critical();
             }
+            self.game_state = GameState::GameEnd;
+            SavedMatch::delete();
+            return;
+        }
+        let action = if let Some(ai) = self.ais[duel.current_nerd] {
+            self.ai_timer += 1;
+            (self.ai_timer >= self.options.ai_delay()).then(|| ai.choose_action(duel))
+        } else {
+            let nerd = duel.nerds[duel.current_nerd];
+            let usable = |action: usize| {
+                self.unlocks
+                    .is_unlocked(Unlockable::Action(nerd.actions[action].base_name()))
+            };
+            if self.tui.hint_pressed() {
+                let analysis = Analysis::new(&duel.nerds, duel.current_nerd, HINT_DEPTH);
+                if let Some((action, win_chance)) = analysis.best_of(usable) {
+                    self.tui.set_hint(vec![
+                        format!("Hint: {}", nerd.actions[action].name()),
+                        format!("({:.0}% chance of winning)", win_chance * 100.0),
+                    ]);
+                }
+            }
+            if self.tui.taunt_pressed() && duel.can_taunt(duel.current_nerd) {
+                self.game_state = GameState::InGame(InGameState::Taunting);
+                return;
+            }
+            match self.tui.action_chosen() {
+                Some(action) if !usable(action) => {
+                    self.tui.set_hint(vec![LOCKED_ACTION_MESSAGE.to_string()]);
+                    None
+                }
+                action => action,
+            }
+        };
+        if let Some(action) = action {
+            self.tui.set_hint(Vec::new());
+            self.ai_timer = 0;
+            if duel.choose_action(action) {
+                self.game_state = GameState::InGame(InGameState::Mathing);
+            }
+        }
+    }
+
+    // Returns the lines announcing what was just unlocked
+    fn unlock_lines(unlocked: Vec<Unlockable>) -> Vec<String> {
+        unlocked
+            .iter()
+            .map(|unlockable| format!("Unlocked: {}", unlockable.name()))
+            .collect()
+    }
+
+    // Updates the game when entering math answer
+    fn update_mathing(&mut self) {
+        let Some(duel) = &mut self.duel else {
+            return;
+        };
+        let ai = self.ais[duel.current_nerd];
+        let num = if let Some(ai) = ai {
+            self.ai_timer += 1;
+            (self.ai_timer >= self.options.ai_delay()).then(|| ai.answer(duel))
+        } else if self.tui.back() {
+            self.game_state = GameState::InGame(InGameState::Choosing);
+            return;
+        } else {
+            self.tui.math_chosen()
+        };
+        if let Some(num) = num {
+            if ai.is_none() && duel.multiple_choice {
+                self.stats.record(duel.mistake(num));
+            }
+            let critical = duel.critical();
+            if duel.submit(num) && critical {
+                self.tui.flash_critical();
+            }
+            self.ai_timer = 0;
+            self.game_state = GameState::InGame(InGameState::Choosing);
+        }
+    }
+
+    // Updates the game when choosing a taunt
+    fn update_taunting(&mut self) {
+        let Some(duel) = &mut self.duel else {
+            return;
+        };
+        if self.tui.back() {
+            self.game_state = GameState::InGame(InGameState::Choosing);
+        } else if let Some(taunt) = self.tui.taunt_chosen() {
+            duel.taunt(duel.current_nerd, taunt);
+            self.game_state = GameState::InGame(InGameState::Choosing);
+        }
+    }
+
+    // Updates the game when solving practice problems
+    fn update_practice(&mut self) {
+        let Some(practice) = &mut self.practice else {
+            return;
+        };
+        if self.tui.back() || practice.finished() {
+            self.tui.set_info(practice.summary());
+            self.game_state = GameState::Practice(PracticeState::Summary);
+            return;
         }
+        if let Some(num) = self.tui.math_chosen() {
+            practice.submit(num);
+        }
+        self.tui.set_info(practice.stats());
     }
 }
 
+// Replaces nerd0 and nerd1 in a message template with the names of nerds
+pub fn fill_names(template: &str, names: [&str; 2]) -> String {
+    template
+        .replace("nerd0", names[0])
+        .replace("nerd1", names[1])
+}
+
 // Represents the possible states the game can be in
 #[derive(Copy, Clone)]
 pub enum GameState {
     Intro,
     MainMenu,
+    NerdSelect(GameMode),
+    Dialogue(CampaignScene),
     InGame(InGameState),
     GameEnd,
+    Practice(PracticeState),
+    Paused,
+    Options,
+    Creator,
+    Stats,
 }
 
 // Represents what is going on in game
@@ -168,4 +661,83 @@ pub enum GameState {
 pub enum InGameState {
     Choosing,
     Mathing,
+    Taunting,
+}
+
+// Represents what is going on in practice
+#[derive(Copy, Clone)]
+pub enum PracticeState {
+    Setup,
+    Solving,
+    Summary,
+}
+
+// The ways the game can be played
+#[derive(Copy, Clone, PartialEq)]
+pub enum GameMode {
+    Versus,
+    Campaign,
+}
+
+// The dialogue shown between campaign fights
+#[derive(Copy, Clone)]
+pub enum CampaignScene {
+    Intro,
+    Outro,
+    Defeat,
+    Ending,
+}
+
+// Options that can be chosen in the main menu
+#[derive(Copy, Clone)]
+pub enum MenuOption {
+    ContinueMatch,
+    Versus,
+    NewCampaign,
+    ContinueCampaign,
+    Practice,
+    CreateNerd,
+    Stats,
+    Options,
+}
+
+impl MenuOption {
+    // Returns the text shown for the option
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ContinueMatch => "Continue Match",
+            Self::Versus => "Versus",
+            Self::NewCampaign => "New Campaign",
+            Self::ContinueCampaign => "Continue Campaign",
+            Self::Practice => "Practice",
+            Self::CreateNerd => "Create a Nerd",
+            Self::Stats => "Answer Stats",
+            Self::Options => "Options",
+        }
+    }
+}
+
+// Options that can be chosen in the pause menu
+#[derive(Copy, Clone)]
+pub enum PauseOption {
+    Resume,
+    Restart,
+    SaveMatch,
+    MainMenu,
+    Options,
+    Quit,
+}
+
+impl PauseOption {
+    // Returns the text shown for the option
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Resume => "Resume",
+            Self::Restart => "Restart",
+            Self::SaveMatch => "Save Match",
+            Self::MainMenu => "Return to Main Menu",
+            Self::Options => "Options",
+            Self::Quit => "Quit",
+        }
+    }
 }
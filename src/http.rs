@@ -0,0 +1,124 @@
+use crate::json::Json;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+// Largest request body that will be read
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+
+// A request sent to a server (only what the servers need from it)
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+impl Request {
+    // Reads a request from a connection
+    pub fn read(stream: &TcpStream) -> io::Result<Self> {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "bad request line",
+            ));
+        };
+        let (method, path) = (method.to_string(), path.to_string());
+
+        let mut body_length = 0;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    body_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        let mut body = vec![0; body_length.min(MAX_BODY_LENGTH)];
+        reader.read_exact(&mut body)?;
+        Ok(Self {
+            method,
+            path,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+
+    // Returns the path split into its parts (without the query)
+    pub fn segments(&self) -> Vec<&str> {
+        let path = self.path.split('?').next().unwrap_or_default();
+        path.split('/').filter(|part| !part.is_empty()).collect()
+    }
+
+    // Returns the value of a query parameter (like token in /rooms/1?token=abc)
+    pub fn query(&self, key: &str) -> Option<&str> {
+        let (_, query) = self.path.split_once('?')?;
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value)
+    }
+
+    // Returns the body read as JSON (an empty body is an empty object)
+    pub fn json(&self) -> Option<Json> {
+        if self.body.trim().is_empty() {
+            Some(Json::Object(Vec::new()))
+        } else {
+            Json::parse(&self.body)
+        }
+    }
+}
+
+// A JSON response to a request
+pub struct Response {
+    pub status: u16,
+    pub body: Json,
+}
+
+impl Response {
+    // Creates a successful response
+    pub fn ok(body: Json) -> Self {
+        Self { status: 200, body }
+    }
+
+    // Creates a response for something that went wrong
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: Json::object([("error", Json::string(message))]),
+        }
+    }
+
+    // Writes the response to a connection (which is then closed)
+    pub fn write(&self, mut stream: &TcpStream) -> io::Result<()> {
+        let body = self.body.to_string();
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.status,
+            Self::reason(self.status),
+            body.len()
+        )?;
+        stream.flush()
+    }
+
+    // Returns the text that goes with a status code
+    fn reason(status: u16) -> &'static str {
+        match status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            _ => "Internal Server Error",
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt;
+
+// Deepest that arrays and objects can be nested inside of each other
+const MAX_DEPTH: usize = 64;
+
+// A JSON value
+#[derive(Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    // Creates an object from its keys and values
+    pub fn object<const N: usize>(entries: [(&str, Json); N]) -> Self {
+        Self::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    // Creates a string
+    pub fn string(text: &str) -> Self {
+        Self::String(text.to_string())
+    }
+
+    // Adds the keys and values of another object to this one, replacing any it already has (if both are objects)
+    pub fn extend(&mut self, other: Json) {
+        if let (Self::Object(entries), Self::Object(other)) = (self, other) {
+            for (key, value) in other {
+                match entries.iter_mut().find(|(entry, _)| *entry == key) {
+                    Some((_, old)) => *old = value,
+                    None => entries.push((key, value)),
+                }
+            }
+        }
+    }
+
+    // Returns the value of a key if this is an object that has it
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Self::Object(entries) => entries
+                .iter()
+                .find(|(entry, _)| entry == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    // Returns the number if this is one
+    pub fn as_number(&self) -> Option<i64> {
+        match self {
+            Self::Number(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    // Returns the text if this is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    // Returns the values if this is an array
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    // Reads a value from JSON text (whole numbers only)
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        (parser.pos == parser.chars.len()).then_some(value)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for Json {
+    fn from(value: i32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Self::Number(value as i64)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+impl fmt::Display for Json {
+    // Writes the value as JSON text
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Number(num) => write!(f, "{num}"),
+            Self::String(text) => write_string(f, text),
+            Self::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+// Writes text as a JSON string with quotes and escapes
+fn write_string(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+// Reads JSON text one character at a time
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    // Reads any value
+    fn value(&mut self) -> Option<Json> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos)? {
+            '{' => self.nested(Self::object),
+            '[' => self.nested(Self::array),
+            '"' => self.string().map(Json::String),
+            't' => self.word("true", Json::Bool(true)),
+            'f' => self.word("false", Json::Bool(false)),
+            'n' => self.word("null", Json::Null),
+            _ => self.number(),
+        }
+    }
+
+    // Reads an object or array inside of everything being read, unless they're nested too deeply
+    // (so a request can't overflow the stack)
+    fn nested(&mut self, read: fn(&mut Self) -> Option<Json>) -> Option<Json> {
+        if self.depth >= MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+        value
+    }
+
+    // Reads an object
+    fn object(&mut self) -> Option<Json> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+        if self.next_is('}') {
+            return Some(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            if !self.next_is(':') {
+                return None;
+            }
+            entries.push((key, self.value()?));
+            if self.next_is('}') {
+                return Some(Json::Object(entries));
+            } else if !self.next_is(',') {
+                return None;
+            }
+        }
+    }
+
+    // Reads an array
+    fn array(&mut self) -> Option<Json> {
+        self.pos += 1;
+        let mut values = Vec::new();
+        if self.next_is(']') {
+            return Some(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            if self.next_is(']') {
+                return Some(Json::Array(values));
+            } else if !self.next_is(',') {
+                return None;
+            }
+        }
+    }
+
+    // Reads a string (the quotes aren't included)
+    fn string(&mut self) -> Option<String> {
+        if self.chars.get(self.pos) != Some(&'"') {
+            return None;
+        }
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            let c = *self.chars.get(self.pos)?;
+            self.pos += 1;
+            match c {
+                '"' => return Some(text),
+                '\\' => {
+                    let escaped = *self.chars.get(self.pos)?;
+                    self.pos += 1;
+                    text.push(match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => {
+                            let code: String =
+                                self.chars.get(self.pos..self.pos + 4)?.iter().collect();
+                            self.pos += 4;
+                            char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+                        }
+                        c => c,
+                    });
+                }
+                c => text.push(c),
+            }
+        }
+    }
+
+    // Reads a whole number
+    fn number(&mut self) -> Option<Json> {
+        let start = self.pos;
+        if self.chars.get(self.pos) == Some(&'-') {
+            self.pos += 1;
+        }
+        while self.chars.get(self.pos).is_some_and(char::is_ascii_digit) {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse().ok().map(Json::Number)
+    }
+
+    // Reads a word like true, false or null
+    fn word(&mut self, word: &str, value: Json) -> Option<Json> {
+        let end = self.pos + word.len();
+        let text: String = self.chars.get(self.pos..end)?.iter().collect();
+        self.pos = end;
+        (text == word).then_some(value)
+    }
+
+    // Skips whitespace, then moves past the next character if it's the given one
+    fn next_is(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Moves past any whitespace
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nesting_up_to_the_limit() {
+        let text = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(Json::parse(&text).is_some());
+    }
+
+    #[test]
+    fn rejects_deeply_nested_input() {
+        let text = format!("{}{}", "[".repeat(MAX_DEPTH + 1), "]".repeat(MAX_DEPTH + 1));
+        assert!(Json::parse(&text).is_none());
+        assert!(Json::parse(&"[".repeat(60_000)).is_none());
+        assert!(Json::parse(&"{\"a\":".repeat(60_000)).is_none());
+    }
+}
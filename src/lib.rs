@@ -0,0 +1,28 @@
+// Everything shared by the game and the other tools
+
+// Things are created with new instead of default
+#![allow(clippy::new_without_default)]
+
+pub mod ai;
+pub mod api;
+pub mod balance;
+pub mod campaign;
+pub mod creator;
+pub mod duel;
+pub mod equation;
+pub mod game;
+pub mod http;
+pub mod json;
+pub mod lobby;
+pub mod nerds;
+pub mod options;
+pub mod practice;
+pub mod rules;
+pub mod saved_match;
+pub mod script;
+pub mod solver;
+pub mod stats;
+pub mod storage;
+pub mod text;
+pub mod tui;
+pub mod unlocks;
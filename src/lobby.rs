@@ -0,0 +1,445 @@
+use crate::api::{self, ApiMatch};
+use crate::http::{Request, Response};
+use crate::json::Json;
+use crate::nerds::Nerd;
+use crate::options::Options;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+// Longest name a player can have
+const MAX_PLAYER_NAME_LENGTH: usize = 20;
+
+// A player sitting in a room, who is known by their secret token
+struct Seat {
+    player: String,
+    nerd: Nerd,
+    token: String,
+    last_seen: Instant,
+}
+
+impl Seat {
+    // Seats a player with a new token
+    fn new(player: String, nerd: Nerd) -> Self {
+        Self {
+            player,
+            nerd,
+            token: new_token(),
+            last_seen: Instant::now(),
+        }
+    }
+}
+
+// Someone watching a room without playing, who is known by their secret token
+struct Spectator {
+    token: String,
+    last_seen: Instant,
+}
+
+// A room in the lobby, which is open until a second player joins and starts the match
+struct Room {
+    seats: Vec<Seat>,
+    game: Option<ApiMatch>,
+    spectators: Vec<Spectator>,
+    history: VecDeque<(Instant, Json)>,
+}
+
+impl Room {
+    // Opens a room with its first player
+    fn new(seat: Seat) -> Self {
+        Self {
+            seats: vec![seat],
+            game: None,
+            spectators: Vec::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    // Returns whether the room is open, playing or finished
+    fn status(&self) -> &'static str {
+        match &self.game {
+            None => "open",
+            Some(game) if game.winner().is_none() => "playing",
+            Some(_) => "finished",
+        }
+    }
+
+    // Returns the seat of the player with a token
+    fn seat(&self, token: Option<&str>) -> Option<usize> {
+        self.seats
+            .iter()
+            .position(|seat| Some(seat.token.as_str()) == token)
+    }
+
+    // Returns the room as it's shown in the lobby
+    fn summary(&self, id: usize) -> Json {
+        let players = self
+            .seats
+            .iter()
+            .map(|seat| Json::string(&seat.player))
+            .collect();
+        let nerds = self
+            .seats
+            .iter()
+            .map(|seat| Json::string(seat.nerd.name))
+            .collect();
+        Json::object([
+            ("room", id.into()),
+            ("status", Json::string(self.status())),
+            ("players", Json::Array(players)),
+            ("nerds", Json::Array(nerds)),
+            ("spectators", self.spectators.len().into()),
+        ])
+    }
+
+    // Returns what the room and its match look like now
+    fn view(&self, id: usize, reveal_critical: bool) -> Json {
+        let mut view = self.summary(id);
+        if let Some(game) = &self.game {
+            view.extend(game.state(reveal_critical));
+            view.extend(game.log());
+        }
+        view
+    }
+
+    // Remembers what the room looks like now so spectators can see it a delay later
+    // (nothing is kept without a delay, since spectators see the room as it is)
+    fn record(&mut self, id: usize, reveal_critical: bool, delay: Duration) {
+        if delay.is_zero() {
+            return;
+        }
+        self.history
+            .push_back((Instant::now(), self.view(id, reveal_critical)));
+        self.forget(delay);
+    }
+
+    // Forgets what the room looked like before the newest view that's at least a delay old
+    fn forget(&mut self, delay: Duration) {
+        while self
+            .history
+            .get(1)
+            .is_some_and(|(time, _)| time.elapsed() >= delay)
+        {
+            self.history.pop_front();
+        }
+    }
+
+    // Returns what the room looked like a delay ago (or when it was opened if that's more recent)
+    fn delayed_view(&mut self, id: usize, reveal_critical: bool, delay: Duration) -> Json {
+        let mut view = if delay.is_zero() {
+            self.view(id, reveal_critical)
+        } else {
+            self.forget(delay);
+            self.history
+                .front()
+                .map_or(Json::Null, |(_, view)| view.clone())
+        };
+        view.extend(Json::object([
+            ("spectators", self.spectators.len().into()),
+            ("delay", (delay.as_secs() as usize).into()),
+        ]));
+        view
+    }
+}
+
+// Rooms of matches between players on other machines (players who go quiet for too long forfeit)
+pub struct Lobby {
+    options: Options,
+    rooms: BTreeMap<usize, Room>,
+    next_id: usize,
+    timeout: Duration,
+    spectator_delay: Duration,
+}
+
+impl Lobby {
+    // Creates an empty lobby that uses the saved options (spectators see everything a delay late)
+    pub fn new(timeout: Duration, spectator_delay: Duration) -> Self {
+        Self {
+            options: Options::load(),
+            rooms: BTreeMap::new(),
+            next_id: 1,
+            timeout,
+            spectator_delay,
+        }
+    }
+
+    // Returns the response to a request
+    pub fn handle(&mut self, request: &Request) -> Response {
+        let Some(body) = request.json() else {
+            return Response::error(400, "The body isn't valid JSON");
+        };
+        let segments = request.segments();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["nerds"]) => Response::ok(api::roster()),
+            ("GET", ["rooms"]) => Response::ok(self.rooms()),
+            ("POST", ["rooms"]) => self.create_room(&body),
+            (method, ["rooms", id, rest @ ..]) => {
+                let Some(id) = id.parse().ok().filter(|id| self.rooms.contains_key(id)) else {
+                    return Response::error(404, "There is no room with that id");
+                };
+                let token = request
+                    .query("token")
+                    .or_else(|| body.get("token").and_then(Json::as_str));
+                match (method, rest) {
+                    ("GET", []) => Response::ok(self.state(id, token)),
+                    ("GET", ["log"]) => Response::ok(self.log(id, token)),
+                    ("POST", ["join"]) => self.join(id, &body),
+                    ("POST", ["action"]) => self.play(id, token, |game| game.choose_action(&body)),
+                    ("POST", ["answer"]) => self.play(id, token, |game| game.submit(&body)),
+                    ("POST", ["taunt"]) => self.taunt(id, token, &body),
+                    ("POST", ["leave"]) => self.leave(id, token),
+                    ("POST", ["watch"]) => self.watch(id),
+                    ("GET", ["watch"]) => self.spectate(id, token),
+                    (
+                        _,
+                        []
+                        | ["log"]
+                        | ["join"]
+                        | ["action"]
+                        | ["answer"]
+                        | ["taunt"]
+                        | ["leave"]
+                        | ["watch"],
+                    ) => Response::error(405, "That method isn't allowed here"),
+                    _ => Response::error(404, "There is nothing here"),
+                }
+            }
+            (_, ["nerds"] | ["rooms"]) => Response::error(405, "That method isn't allowed here"),
+            _ => Response::error(404, "There is nothing here"),
+        }
+    }
+
+    // Forfeits the matches of players who haven't been heard from in too long, closes their rooms,
+    // and stops counting spectators who stopped watching
+    pub fn forfeit_idle(&mut self) {
+        let (timeout, reveal_critical, delay) = (
+            self.timeout,
+            self.options.reveal_critical,
+            self.spectator_delay,
+        );
+        self.rooms.retain(|&id, room| {
+            room.spectators
+                .retain(|spectator| spectator.last_seen.elapsed() <= timeout);
+            let idle: Vec<bool> = room
+                .seats
+                .iter()
+                .map(|seat| seat.last_seen.elapsed() > timeout)
+                .collect();
+            match &mut room.game {
+                Some(game) if game.winner().is_none() => {
+                    if let Some(seat) = idle.iter().position(|&idle| idle) {
+                        game.forfeit(seat);
+                        room.record(id, reveal_critical, delay);
+                    }
+                    true
+                }
+                _ => !idle.iter().all(|&idle| idle),
+            }
+        });
+    }
+
+    // Returns every room in the lobby
+    fn rooms(&self) -> Json {
+        let rooms = self
+            .rooms
+            .iter()
+            .map(|(&id, room)| room.summary(id))
+            .collect();
+        Json::object([("rooms", Json::Array(rooms))])
+    }
+
+    // Opens a room with a player (given as "player") and their nerd (given as "nerd")
+    fn create_room(&mut self, body: &Json) -> Response {
+        let seat = match Self::new_seat(body) {
+            Ok(seat) => seat,
+            Err(response) => return response,
+        };
+        let id = self.next_id;
+        self.next_id += 1;
+        let token = seat.token.clone();
+        let mut room = Room::new(seat);
+        room.record(id, self.options.reveal_critical, self.spectator_delay);
+        self.rooms.insert(id, room);
+        Response {
+            status: 201,
+            body: self.state(id, Some(&token)),
+        }
+    }
+
+    // Adds a second player to an open room and starts its match
+    fn join(&mut self, id: usize, body: &Json) -> Response {
+        let seat = match Self::new_seat(body) {
+            Ok(seat) => seat,
+            Err(response) => return response,
+        };
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        if room.game.is_some() {
+            return Response::error(409, "The room isn't open");
+        }
+        let game = match ApiMatch::new([room.seats[0].nerd, seat.nerd], &self.options) {
+            Ok(game) => game,
+            Err(response) => return response,
+        };
+        let token = seat.token.clone();
+        room.seats.push(seat);
+        room.game = Some(game);
+        room.record(id, self.options.reveal_critical, self.spectator_delay);
+        Response {
+            status: 201,
+            body: self.state(id, Some(&token)),
+        }
+    }
+
+    // Reads a new player and their nerd from a request body
+    fn new_seat(body: &Json) -> Result<Seat, Response> {
+        let player = body
+            .get("player")
+            .and_then(Json::as_str)
+            .map(str::trim)
+            .filter(|player| (1..=MAX_PLAYER_NAME_LENGTH).contains(&player.chars().count()))
+            .ok_or_else(|| {
+                Response::error(
+                    400,
+                    &format!(
+                        "\"player\" has to be a name of 1 to {MAX_PLAYER_NAME_LENGTH} characters"
+                    ),
+                )
+            })?;
+        let nerd = body
+            .get("nerd")
+            .and_then(Json::as_str)
+            .ok_or_else(|| Response::error(400, "\"nerd\" has to be the name of a nerd"))?;
+        Ok(Seat::new(player.to_string(), api::find_nerd(nerd)?))
+    }
+
+    // Lets the player whose turn it is make a move in their room's match
+    fn play(
+        &mut self,
+        id: usize,
+        token: Option<&str>,
+        play: impl FnOnce(&mut ApiMatch) -> Result<(), Response>,
+    ) -> Response {
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        let Some(seat) = room.seat(token) else {
+            return Response::error(403, "That token isn't for a player in this room");
+        };
+        room.seats[seat].last_seen = Instant::now();
+        let Some(game) = &mut room.game else {
+            return Response::error(409, "The match hasn't started yet");
+        };
+        if game.winner().is_none() && game.current_nerd() != seat {
+            return Response::error(403, "It isn't your turn");
+        }
+        if let Err(response) = play(game) {
+            return response;
+        }
+        room.record(id, self.options.reveal_critical, self.spectator_delay);
+        Response::ok(self.state(id, token))
+    }
+
+    // Has a player's nerd say a taunt (players can taunt on either nerd's turn)
+    fn taunt(&mut self, id: usize, token: Option<&str>, body: &Json) -> Response {
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        let Some(seat) = room.seat(token) else {
+            return Response::error(403, "That token isn't for a player in this room");
+        };
+        room.seats[seat].last_seen = Instant::now();
+        let Some(game) = &mut room.game else {
+            return Response::error(409, "The match hasn't started yet");
+        };
+        if let Err(response) = game.taunt(seat, body) {
+            return response;
+        }
+        room.record(id, self.options.reveal_critical, self.spectator_delay);
+        Response::ok(self.state(id, token))
+    }
+
+    // Removes a player from their room (forfeiting if the match is being played)
+    fn leave(&mut self, id: usize, token: Option<&str>) -> Response {
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        let Some(seat) = room.seat(token) else {
+            return Response::error(403, "That token isn't for a player in this room");
+        };
+        match &mut room.game {
+            Some(game) => {
+                game.forfeit(seat);
+                room.record(id, self.options.reveal_critical, self.spectator_delay);
+            }
+            None => {
+                self.rooms.remove(&id);
+            }
+        }
+        Response::ok(Json::object([("left", true.into())]))
+    }
+
+    // Starts watching a room; returns the spectator's token with what they can see
+    fn watch(&mut self, id: usize) -> Response {
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        let token = new_token();
+        room.spectators.push(Spectator {
+            token: token.clone(),
+            last_seen: Instant::now(),
+        });
+        let mut view = Json::object([("token", Json::string(&token))]);
+        view.extend(self.spectate(id, Some(&token)).body);
+        Response {
+            status: 201,
+            body: view,
+        }
+    }
+
+    // Returns what a spectator can see of a room (which also marks them as still watching)
+    fn spectate(&mut self, id: usize, token: Option<&str>) -> Response {
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        let Some(spectator) = room
+            .spectators
+            .iter_mut()
+            .find(|spectator| Some(spectator.token.as_str()) == token)
+        else {
+            return Response::error(403, "That token isn't for a spectator of this room");
+        };
+        spectator.last_seen = Instant::now();
+        Response::ok(room.delayed_view(id, self.options.reveal_critical, self.spectator_delay))
+    }
+
+    // Returns the state of a room and its match to a player (whose token also marks them as still here);
+    // anyone else sees it as late as spectators do
+    fn state(&mut self, id: usize, token: Option<&str>) -> Json {
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        let Some(seat) = room.seat(token) else {
+            return room.delayed_view(id, self.options.reveal_critical, self.spectator_delay);
+        };
+        room.seats[seat].last_seen = Instant::now();
+        let mut state = room.summary(id);
+        state.extend(Json::object([
+            ("seat", seat.into()),
+            ("token", Json::string(&room.seats[seat].token)),
+        ]));
+        if let Some(game) = &room.game {
+            state.extend(game.state(self.options.reveal_critical));
+        }
+        state
+    }
+
+    // Returns every message about what has happened in a room's match (as late as spectators see them,
+    // unless a player's token is given)
+    fn log(&mut self, id: usize, token: Option<&str>) -> Json {
+        let room = self.rooms.get_mut(&id).expect("the room exists");
+        let mut log = Json::object([("room", id.into())]);
+        if room.seat(token).is_some() {
+            if let Some(game) = &room.game {
+                log.extend(game.log());
+            }
+        } else {
+            let view = room.delayed_view(id, self.options.reveal_critical, self.spectator_delay);
+            if let Some(messages) = view.get("messages") {
+                log.extend(Json::object([("messages", messages.clone())]));
+            }
+        }
+        log
+    }
+}
+
+// Returns a new secret token for a player or spectator
+fn new_token() -> String {
+    format!("{:016x}", fastrand::u64(..))
+}
@@ -1,11 +1,35 @@
-mod game;
-mod nerds;
-mod tui;
+use acnd::api::{self, Api};
+use acnd::game::Game;
+use acnd::text::TextGame;
+use std::env;
+use std::process;
 
-use crate::game::Game;
+// Shown when the arguments are wrong
+const USAGE: &str = "Usage: acnd [--api [PORT] | --text]";
 
-// First entry point of the game
+// First entry point of the game (or of the local API with --api [PORT], or of line mode with --text)
 fn main() {
-    let mut game = Game::new();
-    game.main_loop();
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("--api") => {
+            let port = match args.next().map(|port| port.parse()) {
+                None => api::DEFAULT_PORT,
+                Some(Ok(port)) => port,
+                Some(Err(_)) => {
+                    eprintln!("{USAGE}");
+                    process::exit(2);
+                }
+            };
+            if let Err(err) = Api::new().serve(port) {
+                eprintln!("The API couldn't be started: {err}");
+                process::exit(1);
+            }
+        }
+        Some("--text") => TextGame::new().main_loop(),
+        None => Game::new().main_loop(),
+        Some(_) => {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    }
 }
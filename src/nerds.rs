@@ -1,27 +1,108 @@
+use crate::creator;
+use crate::equation::Equation;
+use crate::rules;
+use crate::script::Script;
 use console_engine::Color;
 
 // Color of selected nerd in game and menu
 pub const CURRENT_NERD_COLOR: Color = Color::Green;
 pub const WAITING_NERD_COLOR: Color = Color::Red;
 
-// Values that change damage done
+// Separators used when writing nerds in save files
+const SAVE_SEPARATOR: char = '|';
+const SAVE_VALUE_SEPARATOR: char = ',';
+
+// Values that change damage done (critical hit stats can be different for each nerd)
 const BASE_MULTIPLIER: i32 = 10;
-const CRITICAL_CHANCE: i32 = 20;
-const CRITICAL_MULTIPLIER: i32 = 2;
+const DEFAULT_CRITICAL_CHANCE: i32 = 20;
+const DEFAULT_CRITICAL_MULTIPLIER: i32 = 2;
+
+// Subjects that are super effective against another subject, and how much stronger they are
+const SUPER_EFFECTIVE: [(Subject, Subject); 5] = [
+    (Subject::Science, Subject::Math),
+    (Subject::Math, Subject::Coding),
+    (Subject::Coding, Subject::English),
+    (Subject::English, Subject::Gym),
+    (Subject::Gym, Subject::Science),
+];
+const SUPER_EFFECTIVE_FACTOR: i32 = 2;
+
+// Messages of actions that don't have their own flavor
+const DEFAULT_FLAVOR: Flavor = Flavor::new(
+    &["{attacker} used {action} against {defender}"],
+    &["{attacker} used {action} against {defender}"],
+    &["{attacker} tried to use {action} against {defender}"],
+);
+
+// Taunts of nerds that don't have their own
+const DEFAULT_TAUNTS: [&str; 4] = [
+    "Is that all you've got?",
+    "Do you even math?",
+    "My calculator is scarier than you.",
+    "See you in detention.",
+];
+
+// Every action type and subject (in the order they're cycled through)
+pub const ACTION_TYPES: [ActionType; 4] = [
+    ActionType::Damage,
+    ActionType::Heal,
+    ActionType::Weaken,
+    ActionType::Strengthen,
+];
+pub const SUBJECTS: [Subject; 5] = [
+    Subject::Math,
+    Subject::Science,
+    Subject::English,
+    Subject::Gym,
+    Subject::Coding,
+];
 
 // Used to represent the two players
 pub type Nerds = [Nerd; 2];
 
-// Array of nerds (last is secret)
+// Array of nerds (some have to be unlocked before they can be selected)
 pub const NERDS: [Nerd; 5] = [
     Nerd::new(
         "Joe",
+        "An average nerd who does his homework on time. Good at a bit of everything, great at nothing.",
+        Subject::Math,
         200,
+        5,
         [
-            Action::new("Slap", ActionType::Damage, 3),
-            Action::new("Band-Aid", ActionType::Heal, 2),
-            Action::new("Pinch", ActionType::Weaken, 2),
-            Action::new("Khan Academy", ActionType::Strengthen, 2),
+            Action::new("Slap", ActionType::Damage, 3, Subject::Gym)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} slaps {defender} for {amount} damage",
+                        "{attacker}'s slap leaves a {amount} damage handprint on {defender}",
+                    ],
+                    &[
+                        "{attacker} winds up a {crit}x slap and {defender} sees stars ({amount} damage)",
+                    ],
+                    &[
+                        "{attacker} swings for a slap, misses, and pretends to fix their hair",
+                        "{attacker} slaps the air as hard as they can",
+                    ],
+                )),
+            Action::new("Band-Aid", ActionType::Heal, 2, Subject::Science)
+                .with_flavor(Flavor::new(
+                    &["{attacker} sticks on a Band-Aid and heals {amount}"],
+                    &[
+                        "{attacker} finds a Band-Aid with cartoons on it, which works {crit}x as well (healing {amount})",
+                    ],
+                    &["{attacker} can't get the Band-Aid wrapper open"],
+                )),
+            Action::new("Pinch", ActionType::Weaken, 2, Subject::Gym)
+                .with_flavor(Flavor::new(
+                    &["{attacker} pinches {defender}, who loses {amount} multiplier"],
+                    &["{attacker} does the twisty pinch, and {defender} loses {amount} multiplier"],
+                    &["{attacker} pinches themself by accident"],
+                )),
+            Action::new("Khan Academy", ActionType::Strengthen, 2, Subject::Math)
+                .with_flavor(Flavor::new(
+                    &["{attacker} watches a Khan Academy video and gains {amount} multiplier"],
+                    &["{attacker} earns a Khan Academy badge and gains {amount} multiplier"],
+                    &["{attacker} falls asleep during the Khan Academy video"],
+                )),
         ],
         " / \\
 | \" |
@@ -31,15 +112,54 @@ pub const NERDS: [Nerd; 5] = [
 / | \\
  / \\
 /   \\",
-    ),
+    )
+    .with_taunts([
+        "I did the homework. Did you?",
+        "Average beats you any day.",
+        "I'm telling Khan Academy about this.",
+        "Mid? I'm the whole median.",
+    ]),
     Nerd::new(
         "Isaac",
+        "A self-taught samurai of the meter ruler. Hits hard, but goes down faster than a dropped pencil.",
+        Subject::Science,
         100,
+        8,
         [
-            Action::new("Meter Ruler Katana", ActionType::Damage, 6),
-            Action::new("Self Confidence/Motivation", ActionType::Heal, 2),
-            Action::new("Threaten with Scissors", ActionType::Weaken, 3),
-            Action::new("Steroids", ActionType::Strengthen, 1),
+            Action::new("Meter Ruler Katana", ActionType::Damage, 6, Subject::Math)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} slices {defender} with the meter ruler for {amount} damage",
+                        "{attacker} measures {defender} at exactly {amount} damage",
+                    ],
+                    &[
+                        "{attacker} unleashes the forbidden metric technique on {defender} ({crit}x, {amount} damage)",
+                    ],
+                    &[
+                        "{attacker}'s meter ruler snaps in half",
+                        "{attacker} forgot which end of the ruler is the sharp one",
+                    ],
+                )),
+            Action::new("Self Confidence/Motivation", ActionType::Heal, 2, Subject::English)
+                .with_flavor(Flavor::new(
+                    &["{attacker} believes in themself and heals {amount}"],
+                    &["{attacker} reads a motivational poster {crit} times and heals {amount}"],
+                    &["{attacker} starts doubting themself"],
+                )),
+            Action::new("Threaten with Scissors", ActionType::Weaken, 3, Subject::Gym)
+                .with_flavor(Flavor::new(
+                    &["{attacker} waves scissors at {defender}, who loses {amount} multiplier"],
+                    &["{attacker} runs with scissors, and {defender} loses {amount} multiplier"],
+                    &["{attacker} brought the safety scissors"],
+                )),
+            Action::new("Steroids", ActionType::Strengthen, 1, Subject::Science)
+                .with_flavor(Flavor::new(
+                    &["{attacker} takes something suspicious and gains {amount} multiplier"],
+                    &[
+                        "{attacker} takes {crit} of something suspicious and gains {amount} multiplier",
+                    ],
+                    &["{attacker}'s steroids turn out to be gummy vitamins"],
+                )),
         ],
         " (\")
  \\-/
@@ -49,15 +169,53 @@ pub const NERDS: [Nerd; 5] = [
   |
  / \\
  | |",
-    ),
+    )
+    .with_critical(30, 2)
+    .with_taunts([
+        "My ruler is sharper than your wit.",
+        "A whole meter of pain is coming.",
+        "Measure twice, slap once.",
+        "You're not even on the metric system.",
+    ]),
     Nerd::new(
         "William",
+        "The biggest kid in class, held back twice. Slow to heal but very hard to knock out.",
+        Subject::Gym,
         400,
+        2,
         [
-            Action::new("Curse/Swear Words", ActionType::Damage, 3),
-            Action::new("Meditation", ActionType::Heal, 1),
-            Action::new("Intimidating Stare", ActionType::Weaken, 1),
-            Action::new("Inflatable Dumbbells", ActionType::Strengthen, 3),
+            Action::new("Curse/Swear Words", ActionType::Damage, 3, Subject::English)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} says a word that can't be repeated, dealing {amount} damage to {defender}",
+                    ],
+                    &[
+                        "{attacker} says the worst word ever, and {defender}'s ears take {amount} damage",
+                    ],
+                    &["{attacker} tries to swear but only says \"fudge\""],
+                )),
+            Action::new("Meditation", ActionType::Heal, 1, Subject::Gym)
+                .with_flavor(Flavor::new(
+                    &["{attacker} meditates and heals {amount}"],
+                    &["{attacker} reaches inner peace and heals {amount}"],
+                    &["{attacker} can't stop thinking about lunch"],
+                )),
+            Action::new("Intimidating Stare", ActionType::Weaken, 1, Subject::Gym)
+                .with_flavor(Flavor::new(
+                    &["{attacker} stares down {defender}, who loses {amount} multiplier"],
+                    &[
+                        "{attacker} stares without blinking for a whole minute, and {defender} loses {amount} multiplier",
+                    ],
+                    &["{attacker} blinks first"],
+                )),
+            Action::new("Inflatable Dumbbells", ActionType::Strengthen, 3, Subject::Gym)
+                .with_flavor(Flavor::new(
+                    &["{attacker} lifts the inflatable dumbbells and gains {amount} multiplier"],
+                    &[
+                        "{attacker} inflates the dumbbells all the way and gains {amount} multiplier",
+                    ],
+                    &["{attacker}'s dumbbells pop"],
+                )),
         ],
         "   __
   /''\\
@@ -67,15 +225,58 @@ pub const NERDS: [Nerd; 5] = [
   \\  /
   //\\\\
 _//  \\\\_",
-    ),
+    )
+    .with_critical(10, 3)
+    .with_taunts([
+        "I've been in this grade longer than you.",
+        "Stare harder. I dare you.",
+        "These dumbbells aren't even inflated yet.",
+        "Held back twice, never held back.",
+    ]),
     Nerd::new(
         "Suzie",
+        "The teacher's pet. Weak insults, but always has a first aid kit and a teacher to tell.",
+        Subject::English,
         200,
+        6,
         [
-            Action::new("Insult", ActionType::Damage, 1),
-            Action::new("First Aid Kit", ActionType::Heal, 4),
-            Action::new("Threaten to Tell Teacher", ActionType::Weaken, 1),
-            Action::new("Watch Dhar Mann Video", ActionType::Strengthen, 3),
+            Action::new("Insult", ActionType::Damage, 1, Subject::English)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} calls {defender} a big meanie for {amount} damage",
+                        "{attacker} says {defender}'s shoes are ugly, dealing {amount} damage",
+                    ],
+                    &[
+                        "{attacker} makes fun of {defender}'s handwriting, and that one hurts ({amount} damage)",
+                    ],
+                    &["{attacker}'s insult is actually kind of a compliment"],
+                )),
+            Action::new("First Aid Kit", ActionType::Heal, 4, Subject::Science)
+                .with_flavor(Flavor::new(
+                    &["{attacker} opens the first aid kit and heals {amount}"],
+                    &["{attacker} finds the good ice pack and heals {amount}"],
+                    &["{attacker}'s first aid kit only has expired gauze"],
+                )),
+            Action::new("Threaten to Tell Teacher", ActionType::Weaken, 1, Subject::English)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} threatens to tell the teacher, and {defender} loses {amount} multiplier",
+                    ],
+                    &[
+                        "{attacker} tells the principal instead, and {defender} loses {amount} multiplier",
+                    ],
+                    &["{attacker} tries to tell the teacher, but the teacher isn't in the room"],
+                )),
+            Action::new("Watch Dhar Mann Video", ActionType::Strengthen, 3, Subject::Coding)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} learns a lesson from a Dhar Mann video and gains {amount} multiplier",
+                    ],
+                    &[
+                        "{attacker} watches the Dhar Mann video {crit} times and gains {amount} multiplier",
+                    ],
+                    &["{attacker} gets an unskippable ad instead of the video"],
+                )),
         ],
         " //\"\\\\
 / \\~/ \\
@@ -85,15 +286,55 @@ _//  \\\\_",
    |
   / \\
  /   \\",
-    ),
+    )
+    .with_critical(25, 2)
+    .with_taunts([
+        "I'm telling!",
+        "The teacher likes me more.",
+        "That's going in my diary.",
+        "Did you even raise your hand?",
+    ]),
     Nerd::new(
         "Yamin",
+        "Nobody knows what he does. He doesn't know either. Probably the developer.",
+        Subject::Coding,
         1000000,
+        1,
         [
-            Action::new("Do literally nothing", ActionType::Damage, 100),
-            Action::new("Do pretty much nothing", ActionType::Heal, 100),
-            Action::new("Do basically nothing", ActionType::Weaken, 100),
-            Action::new("Do figuratively nothing", ActionType::Strengthen, 100),
+            Action::new("Do literally nothing", ActionType::Damage, 100, Subject::Coding)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} does literally nothing, and {defender} takes {amount} damage anyway",
+                    ],
+                    &[
+                        "{attacker} does nothing {crit}x as hard, and {defender} takes {amount} damage",
+                    ],
+                    &["{attacker} tries to do nothing but accidentally does something"],
+                )),
+            Action::new("Do pretty much nothing", ActionType::Heal, 100, Subject::Coding)
+                .with_flavor(Flavor::new(
+                    &["{attacker} does pretty much nothing and heals {amount}"],
+                    &["{attacker} does pretty much nothing {crit}x over and heals {amount}"],
+                    &["{attacker} forgets to do nothing"],
+                )),
+            Action::new("Do basically nothing", ActionType::Weaken, 100, Subject::Coding)
+                .with_flavor(Flavor::new(
+                    &[
+                        "{attacker} does basically nothing, and {defender} loses {amount} multiplier",
+                    ],
+                    &[
+                        "{attacker} does basically nothing so well that {defender} loses {amount} multiplier",
+                    ],
+                    &["{attacker}'s nothing has a bug in it"],
+                )),
+            Action::new("Do figuratively nothing", ActionType::Strengthen, 100, Subject::Coding)
+                .with_flavor(Flavor::new(
+                    &["{attacker} does figuratively nothing and gains {amount} multiplier"],
+                    &[
+                        "{attacker} does figuratively nothing on a Friday and gains {amount} multiplier",
+                    ],
+                    &["{attacker} pushes nothing to production, and it breaks"],
+                )),
         ],
         " ____
 /    \\
@@ -103,16 +344,30 @@ _//  \\\\_",
 / || \\
  /  \\
 ^    ^",
-    ),
+    )
+    .with_critical(50, 3)
+    .with_taunts([
+        "It works on my machine.",
+        "That's not a bug, it's a feature.",
+        "I'll patch you out in the next update.",
+        "Have you tried turning yourself off and on again?",
+    ]),
 ];
 
 // A character/player with their stats
 #[derive(Copy, Clone)]
 pub struct Nerd {
     pub name: &'static str,
+    pub bio: &'static str,
+    pub subject: Subject,
     pub health: i32,
+    pub max_health: i32,
+    pub speed: i32,
     pub multiplier: i32,
+    pub critical_chance: i32,
+    pub critical_multiplier: i32,
     pub actions: [Action; 4],
+    pub taunts: [&'static str; 4],
     pub sprite: &'static str,
 }
 
@@ -120,83 +375,282 @@ impl Nerd {
     // Creates a new nerd
     pub const fn new(
         name: &'static str,
+        bio: &'static str,
+        subject: Subject,
         health: i32,
+        speed: i32,
         actions: [Action; 4],
         sprite: &'static str,
     ) -> Self {
         Self {
             name,
+            bio,
+            subject,
             health,
+            max_health: health,
+            speed,
             multiplier: BASE_MULTIPLIER,
+            critical_chance: DEFAULT_CRITICAL_CHANCE,
+            critical_multiplier: DEFAULT_CRITICAL_MULTIPLIER,
             actions,
+            taunts: DEFAULT_TAUNTS,
             sprite,
         }
     }
 
-    // Returns the equation to be answered and the answer to it
-    pub fn equation(&self, action: usize, nerd: &Nerd, critical: bool) -> (String, i32) {
-        let critical = if critical { CRITICAL_MULTIPLIER } else { 1 };
+    // Returns the nerd with a different chance (out of 100) and multiplier for critical hits
+    pub const fn with_critical(mut self, chance: i32, multiplier: i32) -> Self {
+        self.critical_chance = chance;
+        self.critical_multiplier = multiplier;
+        self
+    }
+
+    // Returns the nerd with their own taunts
+    pub const fn with_taunts(mut self, taunts: [&'static str; 4]) -> Self {
+        self.taunts = taunts;
+        self
+    }
+
+    // Returns the equation to be answered (written by the action's script if it has one),
+    // otherwise what's wrong with the script
+    pub fn equation(&self, action: usize, nerd: &Nerd, critical: bool) -> Result<Equation, String> {
+        if let Some(script) = self.actions[action].script() {
+            let (text, answer) = script?.equation(self, nerd, action, critical)?;
+            return Ok(Equation::written(text, answer));
+        }
+        let is_critical = critical;
+        let critical = if critical {
+            self.critical_multiplier
+        } else {
+            1
+        };
+        let effectiveness = self.effectiveness(action, nerd);
+        let action = self.actions[action];
+        let (min_multiplier, max_multiplier) = rules::multiplier_range();
+        let equation = match action.action_type {
+            ActionType::Damage => {
+                let (min, max) = rules::health_range(nerd);
+                let mut factors = vec![action.value, self.multiplier, critical];
+                if effectiveness != 1 {
+                    factors.push(effectiveness);
+                }
+                Equation::new(nerd.health, true, factors).clamped(min, max)
+            }
+            ActionType::Heal => {
+                let (min, max) = rules::health_range(self);
+                Equation::new(
+                    self.health,
+                    false,
+                    vec![action.value, self.multiplier, critical],
+                )
+                .clamped(min, max)
+            }
+            ActionType::Weaken => {
+                let mut factors = vec![action.value, critical];
+                if effectiveness != 1 {
+                    factors.push(effectiveness);
+                }
+                Equation::new(nerd.multiplier, true, factors)
+                    .clamped(min_multiplier, max_multiplier)
+            }
+            ActionType::Strengthen => {
+                Equation::new(self.multiplier, false, vec![action.value, critical])
+                    .clamped(min_multiplier, max_multiplier)
+            }
+        };
+        // The critical multiplier comes right after the value (and the multiplier for health)
+        let critical_factor = match action.action_type {
+            ActionType::Damage | ActionType::Heal => 2,
+            ActionType::Weaken | ActionType::Strengthen => 1,
+        };
+        if is_critical {
+            Ok(equation.with_critical(critical_factor))
+        } else {
+            Ok(equation)
+        }
+    }
+
+    // Returns how many times stronger an action is because of its subject against another nerd's subject
+    pub fn effectiveness(&self, action: usize, nerd: &Nerd) -> i32 {
         let action = self.actions[action];
-        match action.action_type {
-            ActionType::Damage => (
-                format!(
-                    "{} - {} * {} * {}",
-                    nerd.health, action.value, self.multiplier, critical
-                ),
-                nerd.health - action.value * self.multiplier * critical,
-            ),
-            ActionType::Heal => (
-                format!(
-                    "{} + {} * {} * {}",
-                    self.health, action.value, self.multiplier, critical
-                ),
-                self.health + action.value * self.multiplier * critical,
-            ),
-            ActionType::Weaken => (
-                format!("{} - {} * {}", nerd.multiplier, action.value, critical),
-                nerd.multiplier - action.value * critical,
-            ),
-            ActionType::Strengthen => (
-                format!("{} + {} * {}", self.multiplier, action.value, critical),
-                self.multiplier + action.value * critical,
-            ),
+        let against_other = matches!(action.action_type, ActionType::Damage | ActionType::Weaken);
+        if against_other && SUPER_EFFECTIVE.contains(&(action.subject, nerd.subject)) {
+            SUPER_EFFECTIVE_FACTOR
+        } else {
+            1
+        }
+    }
+
+    // Returns the nerd with its health and multiplier multiplied (bigger numbers make harder math)
+    pub fn scaled(mut self, factor: i32) -> Self {
+        self.health = self.health.saturating_mul(factor);
+        self.max_health = self.max_health.saturating_mul(factor);
+        self.multiplier = self.multiplier.saturating_mul(factor);
+        rules::bounded(self)
+    }
+
+    // Returns the nerd with new health and action values no higher than the given value
+    pub fn weakened(mut self, health: i32, max_value: i32) -> Self {
+        self.health = health;
+        self.max_health = health;
+        for action in &mut self.actions {
+            action.value = action.value.min(max_value);
         }
+        self
     }
 
-    // Returns a critical hit multiplier
-    pub fn critical() -> bool {
-        fastrand::i32(0..100) < CRITICAL_CHANCE
+    // Returns the stats of the nerd written for a save file
+    pub fn save_text(&self) -> String {
+        let values: Vec<String> = self
+            .actions
+            .iter()
+            .map(|action| action.value.to_string())
+            .collect();
+        format!(
+            "{}{SAVE_SEPARATOR}{}{SAVE_SEPARATOR}{}{SAVE_SEPARATOR}{}{SAVE_SEPARATOR}{}",
+            self.name,
+            self.health,
+            self.multiplier,
+            values.join(&SAVE_VALUE_SEPARATOR.to_string()),
+            self.max_health
+        )
     }
 
-    // Uses the given action index
+    // Returns the roster (or created) nerd with stats read from a save file
+    pub fn from_save(text: &str) -> Option<Self> {
+        let mut parts = text.split(SAVE_SEPARATOR);
+        let name = parts.next()?;
+        let mut nerd = NERDS
+            .into_iter()
+            .find(|nerd| nerd.name == name)
+            .or_else(|| {
+                creator::load_nerds()
+                    .into_iter()
+                    .find(|nerd| nerd.name == name)
+            })?;
+        nerd.health = parts.next()?.parse().ok()?;
+        nerd.multiplier = parts.next()?.parse().ok()?;
+        let mut values = parts.next()?.split(SAVE_VALUE_SEPARATOR);
+        for action in &mut nerd.actions {
+            action.value = values.next()?.parse().ok()?;
+        }
+        nerd.max_health = parts
+            .next()
+            .and_then(|max_health| max_health.parse().ok())
+            .unwrap_or(nerd.max_health.max(nerd.health));
+        Some(rules::bounded(nerd))
+    }
+
+    // Returns the index of the roster nerd with the name
+    pub fn roster_index(name: &str) -> Option<usize> {
+        NERDS.iter().position(|nerd| nerd.name == name)
+    }
+
+    // Returns whether the nerd's next action is a critical hit
+    pub fn roll_critical(&self) -> bool {
+        fastrand::i32(0..100) < self.critical_chance
+    }
+
+    // Uses the given action index; returns messages about what happened
     pub fn use_action(
         &mut self,
         action: usize,
         value: i32,
         critical: bool,
         nerd: &mut Nerd,
-    ) -> String {
+    ) -> Vec<String> {
+        let stat = self.action_stat(action, nerd);
+        let logs = self.apply_action(action, value, critical, nerd);
+        let amount = stat
+            .abs_diff(self.action_stat(action, nerd))
+            .try_into()
+            .unwrap_or(i32::MAX);
+        let outcome = if critical {
+            Outcome::Critical
+        } else {
+            Outcome::Hit
+        };
+        let mut messages = vec![self.action_message(action, outcome, amount, nerd)];
+        messages.extend(logs);
+        messages
+    }
+
+    // Sets the stat changed by an action to the answer of its equation (or runs the action's script);
+    // returns the messages its script logged
+    pub fn apply_action(
+        &mut self,
+        action: usize,
+        value: i32,
+        critical: bool,
+        nerd: &mut Nerd,
+    ) -> Vec<String> {
+        if let Some(script) = self.actions[action].script() {
+            let (mut attacker, mut defender) = (*self, *nerd);
+            return match script.and_then(|script| {
+                script.apply(&mut attacker, &mut defender, action, critical, value)
+            }) {
+                Ok(logs) => {
+                    (*self, *nerd) = (rules::bounded(attacker), rules::bounded(defender));
+                    logs
+                }
+                Err(err) => vec![format!(
+                    "{}'s script didn't work, so nothing happened ({err})",
+                    self.actions[action].base_name()
+                )],
+            };
+        }
         match self.actions[action].action_type {
             ActionType::Damage => nerd.health = value,
             ActionType::Heal => self.health = value,
             ActionType::Weaken => nerd.multiplier = value,
             ActionType::Strengthen => self.multiplier = value,
         }
-        self.action_message(action, critical, nerd)
+        Vec::new()
+    }
+
+    // Returns a message about an action failing because its equation was answered wrong
+    pub fn failed_message(&self, action: usize, value: i32, nerd: &Nerd) -> String {
+        let amount = self.action_amount(action, value, nerd);
+        self.action_message(action, Outcome::Failed, amount, nerd)
     }
 
-    // Returns a message to be displayed as a result of an action
-    fn action_message(&self, action: usize, critical: bool, nerd: &Nerd) -> String {
+    // Returns how much an action changes its stat when it's set to the answer of its equation
+    fn action_amount(&self, action: usize, value: i32, nerd: &Nerd) -> i32 {
+        value
+            .abs_diff(self.action_stat(action, nerd))
+            .try_into()
+            .unwrap_or(i32::MAX)
+    }
+
+    // Returns the stat that an action's type changes
+    fn action_stat(&self, action: usize, nerd: &Nerd) -> i32 {
+        match self.actions[action].action_type {
+            ActionType::Damage => nerd.health,
+            ActionType::Heal => self.health,
+            ActionType::Weaken => nerd.multiplier,
+            ActionType::Strengthen => self.multiplier,
+        }
+    }
+
+    // Returns a message to be displayed as a result of an action (from a random template of its flavor)
+    fn action_message(&self, action: usize, outcome: Outcome, amount: i32, nerd: &Nerd) -> String {
+        let templates = self.actions[action].flavor.templates(outcome);
+        let template = templates[fastrand::usize(..templates.len())];
+        let message = template
+            .replace("{attacker}", self.name)
+            .replace("{defender}", nerd.name)
+            .replace("{action}", &self.actions[action].name())
+            .replace("{amount}", &amount.to_string())
+            .replace("{crit}", &self.critical_multiplier.to_string());
+        let suffix = match outcome {
+            Outcome::Hit => "",
+            Outcome::Critical => " (CRITICAL!!!)",
+            Outcome::Failed => return format!("{message} (wrong answer)"),
+        };
         format!(
-            "{} used {} against {}{}",
-            self.name,
-            self.actions[action].name(),
-            nerd.name,
-            if critical
-                && (self.actions[action].action_type == ActionType::Weaken
-                    || self.actions[action].action_type == ActionType::Strengthen)
-            {
-                " (CRITICAL!!!)"
+            "{message}{suffix}{}",
+            if self.effectiveness(action, nerd) > 1 {
+                " It's super effective!"
             } else {
                 ""
             }
@@ -210,18 +664,68 @@ pub struct Action {
     name: &'static str,
     action_type: ActionType,
     value: i32,
+    subject: Subject,
+    flavor: Flavor,
+    script: Option<&'static Result<Script, String>>,
 }
 
 impl Action {
     // Creates new stats for action
-    pub const fn new(name: &'static str, action_type: ActionType, value: i32) -> Self {
+    pub const fn new(
+        name: &'static str,
+        action_type: ActionType,
+        value: i32,
+        subject: Subject,
+    ) -> Self {
         Self {
             name,
             action_type,
             value,
+            subject,
+            flavor: DEFAULT_FLAVOR,
+            script: None,
         }
     }
 
+    // Returns the action with its own messages
+    pub const fn with_flavor(mut self, flavor: Flavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    // Returns the action with a script that writes its equation and decides what it does
+    // (its type is still used for its name, messages and power)
+    pub const fn with_script(mut self, script: &'static Result<Script, String>) -> Self {
+        self.script = Some(script);
+        self
+    }
+
+    // Returns the action's script (if it has one), otherwise what's wrong with it
+    pub fn script(&self) -> Option<Result<&'static Script, String>> {
+        self.script
+            .map(|script| script.as_ref().map_err(Clone::clone))
+    }
+
+    // Returns the subject of the action
+    pub fn subject(&self) -> Subject {
+        self.subject
+    }
+
+    // Returns the type of the action
+    pub fn action_type(&self) -> ActionType {
+        self.action_type
+    }
+
+    // Returns the amount of the action
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    // Returns the name of the action without a suffix
+    pub fn base_name(&self) -> &'static str {
+        self.name
+    }
+
     // Returns the name of the action with a suffix
     pub fn name(&self) -> String {
         match self.action_type {
@@ -233,6 +737,47 @@ impl Action {
     }
 }
 
+// Templates of the messages shown when an action is used, one of which is picked at random
+// ({attacker}, {defender}, {action}, {amount} and {crit} are replaced with what happened)
+#[derive(Copy, Clone)]
+pub struct Flavor {
+    hit: &'static [&'static str],
+    critical: &'static [&'static str],
+    failed: &'static [&'static str],
+}
+
+impl Flavor {
+    // Creates the templates for normal hits, critical hits and wrong answers (each needs at least one)
+    pub const fn new(
+        hit: &'static [&'static str],
+        critical: &'static [&'static str],
+        failed: &'static [&'static str],
+    ) -> Self {
+        Self {
+            hit,
+            critical,
+            failed,
+        }
+    }
+
+    // Returns the templates for an outcome
+    fn templates(&self, outcome: Outcome) -> &'static [&'static str] {
+        match outcome {
+            Outcome::Hit => self.hit,
+            Outcome::Critical => self.critical,
+            Outcome::Failed => self.failed,
+        }
+    }
+}
+
+// What happened when an action was used
+#[derive(Copy, Clone, PartialEq)]
+pub enum Outcome {
+    Hit,
+    Critical,
+    Failed,
+}
+
 // Possible actions that can be done with their stats
 #[derive(Copy, Clone, PartialEq)]
 pub enum ActionType {
@@ -241,3 +786,38 @@ pub enum ActionType {
     Weaken,
     Strengthen,
 }
+
+impl ActionType {
+    // Returns the name of the action type
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Damage => "Damage",
+            Self::Heal => "Heal",
+            Self::Weaken => "Weaken",
+            Self::Strengthen => "Strengthen",
+        }
+    }
+}
+
+// School subjects that nerds are good at and actions are about
+#[derive(Copy, Clone, PartialEq)]
+pub enum Subject {
+    Math,
+    Science,
+    English,
+    Gym,
+    Coding,
+}
+
+impl Subject {
+    // Returns the name of the subject
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Math => "Math",
+            Self::Science => "Science",
+            Self::English => "English",
+            Self::Gym => "Gym",
+            Self::Coding => "Coding",
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use crate::nerds::Nerds;
+use crate::storage::Save;
+use crate::unlocks::{Unlockable, Unlocks};
+
+// Name of the file the options are saved in
+const SAVE_NAME: &str = "options";
+
+// Frames the AI waits before doing anything at each speed
+const AI_SPEEDS: [(&str, usize); 3] = [("Slow", 120), ("Normal", 60), ("Fast", 20)];
+const DEFAULT_AI_SPEED: usize = 1;
+
+// Rules for which nerd goes first
+const FIRST_TURNS: [FirstTurn; 3] = [FirstTurn::Speed, FirstTurn::CoinFlip, FirstTurn::LastLoser];
+
+// Ways nerds can be selected for versus matches
+const SELECTION_MODES: [SelectionMode; 3] = [
+    SelectionMode::Standard,
+    SelectionMode::Blind,
+    SelectionMode::Draft,
+];
+
+// Settings that can be changed from the menus
+#[derive(Copy, Clone)]
+pub struct Options {
+    pub adaptive_difficulty: bool,
+    pub selection_mode: SelectionMode,
+    pub mirror_matches: bool,
+    pub reveal_critical: bool,
+    pub first_turn: FirstTurn,
+    pub extra_turns: bool,
+    pub multiple_choice: bool,
+    pub missing_numbers: bool,
+    ai_speed: usize,
+}
+
+impl Options {
+    // Loads the saved options, otherwise the defaults
+    pub fn load() -> Self {
+        let defaults = Self {
+            adaptive_difficulty: true,
+            selection_mode: SelectionMode::Standard,
+            mirror_matches: true,
+            reveal_critical: false,
+            first_turn: FirstTurn::Speed,
+            extra_turns: false,
+            multiple_choice: false,
+            missing_numbers: false,
+            ai_speed: DEFAULT_AI_SPEED,
+        };
+        let Some(save) = Save::load(SAVE_NAME) else {
+            return defaults;
+        };
+        Self {
+            adaptive_difficulty: save
+                .get_parsed("adaptive_difficulty")
+                .unwrap_or(defaults.adaptive_difficulty),
+            selection_mode: save
+                .get_parsed::<usize>("selection_mode")
+                .and_then(|mode| SELECTION_MODES.get(mode).copied())
+                .unwrap_or(defaults.selection_mode),
+            mirror_matches: save
+                .get_parsed("mirror_matches")
+                .unwrap_or(defaults.mirror_matches),
+            reveal_critical: save
+                .get_parsed("reveal_critical")
+                .unwrap_or(defaults.reveal_critical),
+            first_turn: save
+                .get_parsed::<usize>("first_turn")
+                .and_then(|rule| FIRST_TURNS.get(rule).copied())
+                .unwrap_or(defaults.first_turn),
+            extra_turns: save
+                .get_parsed("extra_turns")
+                .unwrap_or(defaults.extra_turns),
+            multiple_choice: save
+                .get_parsed("multiple_choice")
+                .unwrap_or(defaults.multiple_choice),
+            missing_numbers: save
+                .get_parsed("missing_numbers")
+                .unwrap_or(defaults.missing_numbers),
+            ai_speed: save
+                .get_parsed("ai_speed")
+                .filter(|&speed| speed < AI_SPEEDS.len())
+                .unwrap_or(defaults.ai_speed),
+        }
+    }
+
+    // Saves the options
+    pub fn save(&self) {
+        let mut save = Save::new();
+        save.set("adaptive_difficulty", self.adaptive_difficulty);
+        save.set("ai_speed", self.ai_speed);
+        save.set("selection_mode", self.selection_mode as usize);
+        save.set("mirror_matches", self.mirror_matches);
+        save.set("reveal_critical", self.reveal_critical);
+        save.set("first_turn", self.first_turn as usize);
+        save.set("extra_turns", self.extra_turns);
+        save.set("multiple_choice", self.multiple_choice);
+        save.set("missing_numbers", self.missing_numbers);
+        let _ = save.write(SAVE_NAME);
+    }
+
+    // Returns the frames the AI waits before doing anything
+    pub fn ai_delay(&self) -> usize {
+        AI_SPEEDS[self.ai_speed].1
+    }
+
+    // Returns the text shown for each option
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!(
+                "Adaptive difficulty: {}",
+                if self.adaptive_difficulty {
+                    "On"
+                } else {
+                    "Off"
+                }
+            ),
+            format!("AI speed: {}", AI_SPEEDS[self.ai_speed].0),
+            format!("Nerd selection: {}", self.selection_mode.name()),
+            format!(
+                "Mirror matches: {}",
+                if self.mirror_matches {
+                    "Allowed"
+                } else {
+                    "Not allowed"
+                }
+            ),
+            format!(
+                "Critical hits: {}",
+                if self.reveal_critical {
+                    "Shown before choosing"
+                } else {
+                    "Hidden until used"
+                }
+            ),
+            format!("First turn: {}", self.first_turn.name()),
+            format!(
+                "Extra turns for faster nerds: {}",
+                if self.extra_turns { "On" } else { "Off" }
+            ),
+            format!(
+                "Answers: {}",
+                if self.multiple_choice {
+                    "Multiple choice"
+                } else {
+                    "Typed"
+                }
+            ),
+            format!(
+                "Equations: {}",
+                if self.missing_numbers {
+                    "Find the missing number"
+                } else {
+                    "Find the result"
+                }
+            ),
+        ]
+    }
+
+    // Changes an option to its next or previous value (skipping locked values)
+    pub fn change(&mut self, option: usize, forward: bool, unlocks: &Unlocks) {
+        match option {
+            0 => self.adaptive_difficulty = !self.adaptive_difficulty,
+            1 => self.ai_speed = Self::cycle(self.ai_speed, AI_SPEEDS.len(), forward),
+            2 => loop {
+                let mode =
+                    Self::cycle(self.selection_mode as usize, SELECTION_MODES.len(), forward);
+                self.selection_mode = SELECTION_MODES[mode];
+                if unlocks.is_unlocked(Unlockable::Mode(self.selection_mode.name())) {
+                    break;
+                }
+            },
+            3 => self.mirror_matches = !self.mirror_matches,
+            4 => self.reveal_critical = !self.reveal_critical,
+            5 => {
+                let rule = Self::cycle(self.first_turn as usize, FIRST_TURNS.len(), forward);
+                self.first_turn = FIRST_TURNS[rule];
+            }
+            6 => self.extra_turns = !self.extra_turns,
+            7 => self.multiple_choice = !self.multiple_choice,
+            8 => self.missing_numbers = !self.missing_numbers,
+            _ => (),
+        }
+    }
+
+    // Returns the next or previous index out of a number of values
+    fn cycle(index: usize, len: usize, forward: bool) -> usize {
+        if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        }
+    }
+}
+
+// How the nerds for a versus match are selected
+#[derive(Copy, Clone, PartialEq)]
+pub enum SelectionMode {
+    Standard,
+    Blind,
+    Draft,
+}
+
+impl SelectionMode {
+    // Returns the name of the selection mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::Blind => "Blind pick",
+            Self::Draft => "Draft and ban",
+        }
+    }
+}
+
+// Rules for which nerd goes first in a duel
+#[derive(Copy, Clone, PartialEq)]
+pub enum FirstTurn {
+    Speed,
+    CoinFlip,
+    LastLoser,
+}
+
+impl FirstTurn {
+    // Returns the name of the rule
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Speed => "Fastest nerd",
+            Self::CoinFlip => "Coin flip",
+            Self::LastLoser => "Loser of the last match",
+        }
+    }
+
+    // Returns which nerd goes first by the rule (given the loser of the last match), and why
+    pub fn first_nerd(&self, nerds: &Nerds, last_loser: Option<usize>) -> (usize, &'static str) {
+        match (self, last_loser) {
+            (Self::Speed, _) if nerds[0].speed != nerds[1].speed => (
+                usize::from(nerds[1].speed > nerds[0].speed),
+                "for being faster",
+            ),
+            (Self::LastLoser, Some(loser)) => (loser, "after losing the last match"),
+            _ => (fastrand::usize(0..2), "after winning a coin flip"),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use crate::nerds::{ActionType, Nerd, NERDS};
+use crate::unlocks::{Unlockable, Unlocks};
+use std::time::{Duration, Instant};
+
+// Choices for what gets practiced (none means every operation)
+pub const OPERATIONS: [Option<ActionType>; 5] = [
+    None,
+    Some(ActionType::Damage),
+    Some(ActionType::Heal),
+    Some(ActionType::Weaken),
+    Some(ActionType::Strengthen),
+];
+pub const DIFFICULTIES: [i32; 4] = [1, 2, 3, 4];
+pub const LENGTHS: [PracticeLength; 4] = [
+    PracticeLength::Problems(10),
+    PracticeLength::Problems(25),
+    PracticeLength::Seconds(60),
+    PracticeLength::Seconds(180),
+];
+
+// Points given for a right answer (multiplied by the difficulty) and for each answer in a streak
+const POINTS: u32 = 10;
+const STREAK_POINTS: u32 = 5;
+
+// How much multipliers can be randomly changed by in problems
+const MULTIPLIER_RANGE: i32 = 5;
+
+// When a practice session ends
+#[derive(Copy, Clone)]
+pub enum PracticeLength {
+    Problems(u32),
+    Seconds(u64),
+}
+
+impl PracticeLength {
+    // Returns the text shown for the length
+    pub fn name(&self) -> String {
+        match self {
+            Self::Problems(problems) => format!("{problems} problems"),
+            Self::Seconds(seconds) => format!("{seconds} seconds"),
+        }
+    }
+}
+
+// What a practice session is made of
+#[derive(Copy, Clone)]
+pub struct PracticeSettings {
+    pub operation: Option<ActionType>,
+    pub difficulty: i32,
+    pub length: PracticeLength,
+}
+
+// Returns the text shown for an operation choice
+pub fn operation_name(operation: Option<ActionType>) -> &'static str {
+    operation.map_or("All", |operation| operation.name())
+}
+
+// A stream of equations to solve without any fighting
+pub struct Practice {
+    settings: PracticeSettings,
+    equation: String,
+    answer: i32,
+    answered: u32,
+    correct: u32,
+    score: u32,
+    streak: u32,
+    best_streak: u32,
+    feedback: String,
+    started: Instant,
+    paused_at: Option<Instant>,
+    paused_for: Duration,
+}
+
+impl Practice {
+    // Starts a new practice session
+    pub fn new(settings: PracticeSettings) -> Self {
+        let mut practice = Self {
+            settings,
+            equation: String::new(),
+            answer: 0,
+            answered: 0,
+            correct: 0,
+            score: 0,
+            streak: 0,
+            best_streak: 0,
+            feedback: String::new(),
+            started: Instant::now(),
+            paused_at: None,
+            paused_for: Duration::ZERO,
+        };
+        practice.next_problem();
+        practice
+    }
+
+    // Creates a new equation the same way nerds do in a duel
+    fn next_problem(&mut self) {
+        let roster: Vec<Nerd> = NERDS
+            .into_iter()
+            .filter(|nerd| Unlocks::is_starting(Unlockable::Nerd(nerd.name)))
+            .collect();
+        let nerd = Self::random_nerd(&roster, self.settings.difficulty);
+        let other = Self::random_nerd(&roster, self.settings.difficulty);
+        let actions: Vec<usize> = (0..nerd.actions.len())
+            .filter(|&i| {
+                self.settings
+                    .operation
+                    .is_none_or(|operation| nerd.actions[i].action_type() == operation)
+            })
+            .collect();
+        let action = actions[fastrand::usize(0..actions.len())];
+        let equation = nerd
+            .equation(action, &other, nerd.roll_critical())
+            .expect("roster nerds have no scripts");
+        self.equation = equation.to_string();
+        self.answer = equation.answer();
+    }
+
+    // Returns a nerd from the roster with random stats
+    fn random_nerd(roster: &[Nerd], difficulty: i32) -> Nerd {
+        let mut nerd = roster[fastrand::usize(0..roster.len())];
+        nerd.health = fastrand::i32(1..=nerd.health);
+        nerd.multiplier += fastrand::i32(-MULTIPLIER_RANGE..=MULTIPLIER_RANGE);
+        nerd.scaled(difficulty)
+    }
+
+    // Returns the settings the session was started with
+    pub fn settings(&self) -> PracticeSettings {
+        self.settings
+    }
+
+    // Stops the timer
+    pub fn pause(&mut self) {
+        self.paused_at.get_or_insert_with(Instant::now);
+    }
+
+    // Starts the timer again
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_for += paused_at.elapsed();
+        }
+    }
+
+    // Returns how long the session has been going for (not counting pauses)
+    fn elapsed(&self) -> Duration {
+        let paused = self.paused_at.map_or(Duration::ZERO, |at| at.elapsed());
+        self.started.elapsed() - self.paused_for - paused
+    }
+
+    // Returns the equation to solve
+    pub fn equation(&self) -> &str {
+        &self.equation
+    }
+
+    // Checks an answer and moves on to the next problem; returns whether it was right
+    pub fn submit(&mut self, num: i32) -> bool {
+        let right = num == self.answer;
+        self.answered += 1;
+        if right {
+            self.correct += 1;
+            self.score += POINTS * self.settings.difficulty as u32 + STREAK_POINTS * self.streak;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+            self.feedback = String::from("Right!");
+        } else {
+            self.streak = 0;
+            self.feedback = format!("Wrong! {} = {}", self.equation, self.answer);
+        }
+        self.next_problem();
+        right
+    }
+
+    // Returns whether the session is over
+    pub fn finished(&self) -> bool {
+        match self.settings.length {
+            PracticeLength::Problems(problems) => self.answered >= problems,
+            PracticeLength::Seconds(seconds) => self.elapsed() >= Duration::from_secs(seconds),
+        }
+    }
+
+    // Returns the percent of answers that were right
+    fn accuracy(&self) -> u32 {
+        (self.correct * 100).checked_div(self.answered).unwrap_or(0)
+    }
+
+    // Returns how much of the session is left
+    fn remaining(&self) -> String {
+        match self.settings.length {
+            PracticeLength::Problems(problems) => {
+                format!("{} problems left", problems.saturating_sub(self.answered))
+            }
+            PracticeLength::Seconds(seconds) => format!(
+                "{} seconds left",
+                seconds.saturating_sub(self.elapsed().as_secs())
+            ),
+        }
+    }
+
+    // Returns the stats shown while practicing
+    pub fn stats(&self) -> Vec<String> {
+        vec![
+            format!(
+                "Score: {} | Streak: {} | Accuracy: {}% ({}/{})",
+                self.score,
+                self.streak,
+                self.accuracy(),
+                self.correct,
+                self.answered
+            ),
+            self.remaining(),
+            self.feedback.clone(),
+        ]
+    }
+
+    // Returns the stats shown after practicing
+    pub fn summary(&self) -> Vec<String> {
+        vec![
+            String::from("Practice over!"),
+            format!(
+                "Operation: {} | Difficulty: {} | Length: {}",
+                operation_name(self.settings.operation),
+                self.settings.difficulty,
+                self.settings.length.name()
+            ),
+            format!("Final score: {}", self.score),
+            format!(
+                "Right answers: {} of {} ({}%)",
+                self.correct,
+                self.answered,
+                self.accuracy()
+            ),
+            format!("Best streak: {}", self.best_streak),
+        ]
+    }
+}
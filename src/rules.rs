@@ -0,0 +1,27 @@
+use crate::nerds::Nerd;
+
+// Limits that keep every stat (and so every equation) in a duel inside of a range
+pub const MIN_HEALTH: i32 = 0;
+pub const MAX_HEALTH: i32 = 1_000_000;
+pub const MIN_MULTIPLIER: i32 = 1;
+pub const MAX_MULTIPLIER: i32 = 1000;
+
+// Returns the lowest and highest health a nerd can have
+pub fn health_range(nerd: &Nerd) -> (i32, i32) {
+    (MIN_HEALTH, nerd.max_health.clamp(MIN_HEALTH, MAX_HEALTH))
+}
+
+// Returns the lowest and highest multiplier a nerd can have
+pub fn multiplier_range() -> (i32, i32) {
+    (MIN_MULTIPLIER, MAX_MULTIPLIER)
+}
+
+// Returns the nerd with its stats moved inside of their ranges
+pub fn bounded(mut nerd: Nerd) -> Nerd {
+    nerd.max_health = nerd.max_health.clamp(MIN_HEALTH, MAX_HEALTH);
+    let (min, max) = health_range(&nerd);
+    nerd.health = nerd.health.clamp(min, max);
+    let (min, max) = multiplier_range();
+    nerd.multiplier = nerd.multiplier.clamp(min, max);
+    nerd
+}
@@ -0,0 +1,65 @@
+use crate::ai::Ai;
+use crate::duel::Duel;
+use crate::storage::Save;
+
+// Name of the file the match is saved in
+const SAVE_NAME: &str = "match";
+
+// Version of the save format (keys added later need defaults so older saves still load)
+const VERSION: u32 = 1;
+
+// Value saved for nerds that aren't controlled by the AI
+const NO_AI: i32 = -1;
+
+// A match in progress that can be saved and continued later
+pub struct SavedMatch {
+    pub duel: Duel,
+    pub ais: [Option<Ai>; 2],
+    pub mathing: bool,
+    pub campaign: bool,
+    pub seed: u64,
+}
+
+impl SavedMatch {
+    // Writes a match (and the state of the random number generator) to its file
+    pub fn save(duel: &Duel, ais: [Option<Ai>; 2], mathing: bool, campaign: bool) {
+        let mut save = Save::new();
+        save.set("version", VERSION);
+        save.set("seed", fastrand::get_seed());
+        save.set("mathing", mathing);
+        save.set("campaign", campaign);
+        for ai in ais {
+            save.set("ai", ai.map_or(NO_AI, |ai| ai.accuracy()));
+        }
+        duel.save(&mut save);
+        let _ = save.write(SAVE_NAME);
+    }
+
+    // Reads the saved match if there is one made by this version or an older one
+    pub fn load() -> Option<Self> {
+        let save = Save::load(SAVE_NAME)?;
+        if save.get_parsed::<u32>("version")? > VERSION {
+            return None;
+        }
+        let mut ais = save
+            .get_all("ai")
+            .map(|ai| ai.parse().ok().filter(|&ai| ai != NO_AI).map(Ai::new));
+        Some(Self {
+            duel: Duel::load(&save)?,
+            ais: [ais.next().flatten(), ais.next().flatten()],
+            mathing: save.get_parsed("mathing").unwrap_or(false),
+            campaign: save.get_parsed("campaign").unwrap_or(false),
+            seed: save.get_parsed("seed")?,
+        })
+    }
+
+    // Returns whether there is a saved match that can be continued
+    pub fn exists() -> bool {
+        Self::load().is_some()
+    }
+
+    // Deletes the saved match
+    pub fn delete() {
+        let _ = Save::remove(SAVE_NAME);
+    }
+}
@@ -0,0 +1,932 @@
+use crate::nerds::Nerd;
+use crate::storage;
+use std::fs;
+
+// Folder (inside the save folder) and extension of script files
+const SCRIPT_DIR: &str = "scripts";
+const SCRIPT_EXTENSION: &str = "txt";
+
+// Most operations (statements run and values worked out) a script can do each time it's run
+pub const MAX_OPERATIONS: usize = 10_000;
+
+// Starts a comment that lasts until the end of the line
+const COMMENT: char = '#';
+
+// Deepest that expressions (and if and repeat blocks) can be nested, and the most tokens an expression
+// can have (so reading, running and dropping a script can't overflow the stack)
+const MAX_DEPTH: usize = 64;
+const MAX_TOKENS: usize = 1000;
+
+// Loads and reads a script from the scripts folder once (it's leaked, since actions only hold scripts that last
+// the whole program); names can only have letters, digits, _ and -, so they can't point outside of the folder
+pub fn load(name: &str) -> Option<&'static Result<Script, String>> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return None;
+    }
+    let path = storage::path(&format!("{SCRIPT_DIR}/{name}"), SCRIPT_EXTENSION);
+    let text = fs::read_to_string(path).ok()?;
+    Some(Box::leak(Box::new(Script::parse(&text))))
+}
+
+// A small program that decides what an action's equation is and what happens when it's answered right.
+// Each line is one of these statements:
+//   let NAME = EXPRESSION           remembers a number
+//   set attacker.STAT = EXPRESSION  changes a stat (health, max_health or multiplier) of either nerd
+//   equation EXPRESSION             the question asked (written with every name replaced by its number)
+//   log TEXT                        adds a message, with {EXPRESSION} replaced by its number
+//   if EXPRESSION / repeat EXPRESSION ... end
+// Expressions use whole numbers, + - * / %, comparisons (1 is true and 0 is false), min, max and abs,
+// the stats of attacker and defender, value (of the action), crit (the critical hit multiplier, or 1),
+// effectiveness, answer (once answered) and names that were let
+pub struct Script {
+    statements: Vec<Statement>,
+}
+
+impl Script {
+    // Reads a script from its text, otherwise says what's wrong with it
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut blocks = vec![Vec::new()];
+        let mut headers = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split(COMMENT).next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |err: String| format!("Line {}: {err}", i + 1);
+            let (word, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+            let statement = match word {
+                "let" => {
+                    let (name, expr) = rest
+                        .split_once('=')
+                        .ok_or_else(|| err("let needs a name, = and a value".to_string()))?;
+                    let name = name.trim();
+                    if !is_name(name)
+                        || name.contains('.')
+                        || Variable::from_name(name).is_some()
+                        || Function::from_name(name).is_some()
+                    {
+                        return Err(err(format!("{name} can't be used as a name")));
+                    }
+                    Statement::Let(name.to_string(), Expr::parse(expr).map_err(err)?)
+                }
+                "set" => {
+                    let (target, expr) = rest
+                        .split_once('=')
+                        .ok_or_else(|| err("set needs a stat, = and a value".to_string()))?;
+                    let target = target.trim();
+                    let Some(Variable::Stat(side, stat)) = Variable::from_name(target) else {
+                        return Err(err(format!("{target} isn't a stat")));
+                    };
+                    if !stat.writable() {
+                        return Err(err(format!("{target} can't be changed")));
+                    }
+                    Statement::Set(side, stat, Expr::parse(expr).map_err(err)?)
+                }
+                "equation" => Statement::Equation(Expr::parse(rest).map_err(err)?),
+                "log" => Statement::Log(LogPart::parse(rest).map_err(err)?),
+                "if" | "repeat" => {
+                    if headers.len() >= MAX_DEPTH {
+                        return Err(err("The blocks are nested too deeply".to_string()));
+                    }
+                    headers.push((word, Expr::parse(rest).map_err(err)?));
+                    blocks.push(Vec::new());
+                    continue;
+                }
+                "end" => {
+                    let (Some((header, expr)), Some(block)) = (headers.pop(), blocks.pop()) else {
+                        return Err(err("end doesn't close an if or repeat".to_string()));
+                    };
+                    if header == "if" {
+                        Statement::If(expr, block)
+                    } else {
+                        Statement::Repeat(expr, block)
+                    }
+                }
+                _ => return Err(err(format!("{word} isn't a statement"))),
+            };
+            blocks
+                .last_mut()
+                .expect("there is always a block")
+                .push(statement);
+        }
+        if !headers.is_empty() {
+            return Err("An if or repeat is missing its end".to_string());
+        }
+        Ok(Self {
+            statements: blocks.pop().unwrap_or_default(),
+        })
+    }
+
+    // Returns the written equation of an action and its answer, without changing anything
+    pub fn equation(
+        &self,
+        attacker: &Nerd,
+        defender: &Nerd,
+        action: usize,
+        critical: bool,
+    ) -> Result<(String, i32), String> {
+        let mut runner = Runner::new([*attacker, *defender], action, critical, None);
+        match runner.run(&self.statements)? {
+            Some((text, answer)) => Ok((text, clamp(answer))),
+            None => Err("The script has no equation".to_string()),
+        }
+    }
+
+    // Changes the stats of the nerds for an action answered right; returns the messages it logged
+    pub fn apply(
+        &self,
+        attacker: &mut Nerd,
+        defender: &mut Nerd,
+        action: usize,
+        critical: bool,
+        answer: i32,
+    ) -> Result<Vec<String>, String> {
+        let mut runner = Runner::new(
+            [*attacker, *defender],
+            action,
+            critical,
+            Some(answer.into()),
+        );
+        runner.run(&self.statements)?;
+        [*attacker, *defender] = runner.nerds;
+        Ok(runner.logs)
+    }
+}
+
+// One line (or block) of a script
+enum Statement {
+    Let(String, Expr),
+    Set(Side, Stat, Expr),
+    Equation(Expr),
+    Log(Vec<LogPart>),
+    If(Expr, Vec<Statement>),
+    Repeat(Expr, Vec<Statement>),
+}
+
+// Text of a log, or an expression written inside of it with {}
+enum LogPart {
+    Text(String),
+    Expr(Expr),
+}
+
+impl LogPart {
+    // Reads the text of a log statement
+    fn parse(text: &str) -> Result<Vec<Self>, String> {
+        let mut parts = Vec::new();
+        let mut rest = text;
+        while let Some((before, after)) = rest.split_once('{') {
+            let (expr, after) = after
+                .split_once('}')
+                .ok_or_else(|| "A { in the log is missing its }".to_string())?;
+            parts.push(Self::Text(before.to_string()));
+            parts.push(Self::Expr(Expr::parse(expr)?));
+            rest = after;
+        }
+        parts.push(Self::Text(rest.to_string()));
+        Ok(parts)
+    }
+}
+
+// Something that works out to a number
+enum Expr {
+    Number(i64),
+    Variable(Variable),
+    Named(String),
+    Negative(Box<Expr>),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+    Brackets(Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+impl Expr {
+    // Reads an expression from text
+    fn parse(text: &str) -> Result<Self, String> {
+        let tokens = tokenize(text)?;
+        if tokens.len() > MAX_TOKENS {
+            return Err("The expression is too long".to_string());
+        }
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = parser.comparison()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(token) => Err(format!("{token} wasn't expected")),
+        }
+    }
+}
+
+// Names that have numbers when a script is run
+#[derive(Copy, Clone)]
+enum Variable {
+    Value,
+    Crit,
+    Effectiveness,
+    Answer,
+    Stat(Side, Stat),
+}
+
+impl Variable {
+    // Returns the built in variable with a name (names that were let aren't built in)
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "value" => Some(Self::Value),
+            "crit" => Some(Self::Crit),
+            "effectiveness" => Some(Self::Effectiveness),
+            "answer" => Some(Self::Answer),
+            _ => {
+                let (side, stat) = name.split_once('.')?;
+                let side = match side {
+                    "attacker" => Side::Attacker,
+                    "defender" => Side::Defender,
+                    _ => return None,
+                };
+                Some(Self::Stat(side, Stat::from_name(stat)?))
+            }
+        }
+    }
+}
+
+// Which nerd a stat belongs to
+#[derive(Copy, Clone)]
+enum Side {
+    Attacker,
+    Defender,
+}
+
+// Stats of nerds that scripts can see (and change some of)
+#[derive(Copy, Clone)]
+enum Stat {
+    Health,
+    MaxHealth,
+    Multiplier,
+    Speed,
+    CriticalChance,
+    CriticalMultiplier,
+}
+
+impl Stat {
+    // Returns the stat with a name
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "health" => Some(Self::Health),
+            "max_health" => Some(Self::MaxHealth),
+            "multiplier" => Some(Self::Multiplier),
+            "speed" => Some(Self::Speed),
+            "critical_chance" => Some(Self::CriticalChance),
+            "critical_multiplier" => Some(Self::CriticalMultiplier),
+            _ => None,
+        }
+    }
+
+    // Returns whether scripts can change the stat
+    fn writable(&self) -> bool {
+        matches!(self, Self::Health | Self::MaxHealth | Self::Multiplier)
+    }
+
+    // Returns the stat of a nerd
+    fn get(&self, nerd: &Nerd) -> i32 {
+        match self {
+            Self::Health => nerd.health,
+            Self::MaxHealth => nerd.max_health,
+            Self::Multiplier => nerd.multiplier,
+            Self::Speed => nerd.speed,
+            Self::CriticalChance => nerd.critical_chance,
+            Self::CriticalMultiplier => nerd.critical_multiplier,
+        }
+    }
+
+    // Changes the stat of a nerd (if it can be changed)
+    fn set(&self, nerd: &mut Nerd, value: i32) {
+        match self {
+            Self::Health => nerd.health = value,
+            Self::MaxHealth => nerd.max_health = value,
+            Self::Multiplier => nerd.multiplier = value,
+            _ => (),
+        }
+    }
+}
+
+// Operators between two numbers
+#[derive(Copy, Clone, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Operator {
+    // Returns the operator written as a token
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "+" => Some(Self::Add),
+            "-" => Some(Self::Subtract),
+            "*" => Some(Self::Multiply),
+            "/" => Some(Self::Divide),
+            "%" => Some(Self::Remainder),
+            "<" => Some(Self::Less),
+            "<=" => Some(Self::LessOrEqual),
+            ">" => Some(Self::Greater),
+            ">=" => Some(Self::GreaterOrEqual),
+            "==" => Some(Self::Equal),
+            "!=" => Some(Self::NotEqual),
+            _ => None,
+        }
+    }
+
+    // Returns how the operator is written
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Remainder => "%",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+        }
+    }
+
+    // Returns the operator used on two numbers
+    fn apply(&self, left: i64, right: i64) -> Result<i64, String> {
+        Ok(match self {
+            Self::Add => left.saturating_add(right),
+            Self::Subtract => left.saturating_sub(right),
+            Self::Multiply => left.saturating_mul(right),
+            Self::Divide | Self::Remainder if right == 0 => {
+                return Err("A number was divided by 0".to_string())
+            }
+            Self::Divide => left.saturating_div(right),
+            Self::Remainder => left.wrapping_rem(right),
+            Self::Less => (left < right).into(),
+            Self::LessOrEqual => (left <= right).into(),
+            Self::Greater => (left > right).into(),
+            Self::GreaterOrEqual => (left >= right).into(),
+            Self::Equal => (left == right).into(),
+            Self::NotEqual => (left != right).into(),
+        })
+    }
+}
+
+// Functions that scripts can call
+#[derive(Copy, Clone)]
+enum Function {
+    Min,
+    Max,
+    Abs,
+}
+
+impl Function {
+    // Returns the function with a name and how many arguments it takes
+    fn from_name(name: &str) -> Option<(Self, usize)> {
+        match name {
+            "min" => Some((Self::Min, 2)),
+            "max" => Some((Self::Max, 2)),
+            "abs" => Some((Self::Abs, 1)),
+            _ => None,
+        }
+    }
+
+    // Returns the name of the function
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Abs => "abs",
+        }
+    }
+
+    // Returns the function used on its arguments
+    fn apply(&self, args: &[i64]) -> i64 {
+        match self {
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+            Self::Abs => args[0].saturating_abs(),
+        }
+    }
+}
+
+// Splits an expression into numbers, names, operators and brackets
+fn tokenize(text: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            while chars
+                .get(i)
+                .is_some_and(|&c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+            {
+                i += 1;
+            }
+        } else if "<>=!".contains(c) && chars.get(i + 1) == Some(&'=') {
+            i += 2;
+        } else if "+-*/%<>(),".contains(c) {
+            i += 1;
+        } else {
+            return Err(format!("{c} can't be used in an expression"));
+        }
+        tokens.push(chars[start..i].iter().collect());
+    }
+    Ok(tokens)
+}
+
+// Returns whether text can be a name
+fn is_name(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+// Reads expressions from tokens (comparisons, then + and -, then * / %, then single values)
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    // Reads a comparison (or anything with more precedence)
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let expr = self.sum()?;
+        self.binary(expr, &["<", "<=", ">", ">=", "==", "!="], Self::sum)
+    }
+
+    // Reads a sum or difference (or anything with more precedence)
+    fn sum(&mut self) -> Result<Expr, String> {
+        let expr = self.product()?;
+        self.binary(expr, &["+", "-"], Self::product)
+    }
+
+    // Reads a product, quotient or remainder (or anything with more precedence)
+    fn product(&mut self) -> Result<Expr, String> {
+        let expr = self.single()?;
+        self.binary(expr, &["*", "/", "%"], Self::single)
+    }
+
+    // Keeps reading operators (from a list) and what comes after them
+    fn binary(
+        &mut self,
+        mut expr: Expr,
+        operators: &[&str],
+        next: fn(&mut Self) -> Result<Expr, String>,
+    ) -> Result<Expr, String> {
+        while let Some(token) = self
+            .tokens
+            .get(self.pos)
+            .filter(|token| operators.contains(&token.as_str()))
+        {
+            let operator = Operator::from_token(token).expect("operators are known");
+            self.pos += 1;
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(next(self)?));
+        }
+        Ok(expr)
+    }
+
+    // Reads a single value, unless it's nested too deeply inside of others
+    fn single(&mut self) -> Result<Expr, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("The expression is nested too deeply".to_string());
+        }
+        self.depth += 1;
+        let expr = self.value();
+        self.depth -= 1;
+        expr
+    }
+
+    // Reads a number, name, call, negative or bracketed expression
+    fn value(&mut self) -> Result<Expr, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "An expression ended too early".to_string())?;
+        self.pos += 1;
+        if token == "-" {
+            return Ok(Expr::Negative(Box::new(self.single()?)));
+        } else if token == "(" {
+            let expr = self.comparison()?;
+            self.expect(")")?;
+            return Ok(Expr::Brackets(Box::new(expr)));
+        } else if let Ok(num) = token.parse() {
+            return Ok(Expr::Number(num));
+        } else if !is_name(&token) {
+            return Err(format!("{token} wasn't expected"));
+        }
+
+        if let Some((function, arg_count)) = Function::from_name(&token) {
+            self.expect("(")?;
+            let mut args = vec![self.comparison()?];
+            while self.tokens.get(self.pos).is_some_and(|token| token == ",") {
+                self.pos += 1;
+                args.push(self.comparison()?);
+            }
+            self.expect(")")?;
+            if args.len() != arg_count {
+                return Err(format!("{token} takes {arg_count} numbers"));
+            }
+            return Ok(Expr::Call(function, args));
+        }
+        match Variable::from_name(&token) {
+            Some(variable) => Ok(Expr::Variable(variable)),
+            None if !token.contains('.') => Ok(Expr::Named(token)),
+            None => Err(format!("{token} isn't a stat")),
+        }
+    }
+
+    // Moves past a token, otherwise says it's missing
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.tokens.get(self.pos).is_some_and(|next| next == token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("{token} is missing"))
+        }
+    }
+}
+
+// Runs the statements of a script on copies of the nerds, counting its operations
+struct Runner {
+    nerds: [Nerd; 2],
+    value: i64,
+    crit: i64,
+    effectiveness: i64,
+    answer: Option<i64>,
+    names: Vec<(String, i64)>,
+    logs: Vec<String>,
+    operations: usize,
+}
+
+impl Runner {
+    // Creates a runner for an action (with its answer once it's been answered)
+    fn new(nerds: [Nerd; 2], action: usize, critical: bool, answer: Option<i64>) -> Self {
+        let attacker = &nerds[0];
+        Self {
+            value: attacker.actions[action].value().into(),
+            crit: if critical {
+                attacker.critical_multiplier.into()
+            } else {
+                1
+            },
+            effectiveness: attacker.effectiveness(action, &nerds[1]).into(),
+            nerds,
+            answer,
+            names: Vec::new(),
+            logs: Vec::new(),
+            operations: 0,
+        }
+    }
+
+    // Counts an operation, failing once there have been too many
+    fn count(&mut self) -> Result<(), String> {
+        self.operations += 1;
+        if self.operations > MAX_OPERATIONS {
+            return Err(format!(
+                "The script did more than {MAX_OPERATIONS} operations"
+            ));
+        }
+        Ok(())
+    }
+
+    // Runs statements; returns the equation if it's reached before the action is answered
+    fn run(&mut self, statements: &[Statement]) -> Result<Option<(String, i64)>, String> {
+        let answered = self.answer.is_some();
+        for statement in statements {
+            self.count()?;
+            match statement {
+                Statement::Let(name, expr) => {
+                    let value = self.eval(expr)?;
+                    match self.names.iter_mut().find(|(other, _)| other == name) {
+                        Some((_, old)) => *old = value,
+                        None => self.names.push((name.clone(), value)),
+                    }
+                }
+                Statement::Set(side, stat, expr) if answered => {
+                    let value = clamp(self.eval(expr)?);
+                    stat.set(&mut self.nerds[*side as usize], value);
+                }
+                Statement::Equation(expr) if !answered => {
+                    return Ok(Some((self.write(expr)?, self.eval(expr)?)));
+                }
+                Statement::Log(parts) if answered => {
+                    let mut text = String::new();
+                    for part in parts {
+                        match part {
+                            LogPart::Text(part) => text += part,
+                            LogPart::Expr(expr) => text += &self.eval(expr)?.to_string(),
+                        }
+                    }
+                    self.logs.push(text.trim().to_string());
+                }
+                Statement::If(expr, block) => {
+                    if self.eval(expr)? != 0 {
+                        if let Some(equation) = self.run(block)? {
+                            return Ok(Some(equation));
+                        }
+                    }
+                }
+                Statement::Repeat(expr, block) => {
+                    for _ in 0..self.eval(expr)?.max(0) {
+                        self.count()?;
+                        if let Some(equation) = self.run(block)? {
+                            return Ok(Some(equation));
+                        }
+                    }
+                }
+                Statement::Set(..) | Statement::Equation(_) | Statement::Log(_) => (),
+            }
+        }
+        Ok(None)
+    }
+
+    // Works out the number of an expression
+    fn eval(&mut self, expr: &Expr) -> Result<i64, String> {
+        self.count()?;
+        match expr {
+            Expr::Number(num) => Ok(*num),
+            Expr::Variable(variable) => self.variable(*variable),
+            Expr::Named(name) => self
+                .names
+                .iter()
+                .find(|(other, _)| other == name)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| format!("{name} was used before it was let")),
+            Expr::Negative(expr) => Ok(self.eval(expr)?.saturating_neg()),
+            Expr::Binary(left, operator, right) => {
+                let (left, right) = (self.eval(left)?, self.eval(right)?);
+                operator.apply(left, right)
+            }
+            Expr::Brackets(expr) => self.eval(expr),
+            Expr::Call(function, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(function.apply(&args))
+            }
+        }
+    }
+
+    // Returns the number of a built in variable
+    fn variable(&self, variable: Variable) -> Result<i64, String> {
+        match variable {
+            Variable::Value => Ok(self.value),
+            Variable::Crit => Ok(self.crit),
+            Variable::Effectiveness => Ok(self.effectiveness),
+            Variable::Answer => self
+                .answer
+                .ok_or_else(|| "answer was used before the action was answered".to_string()),
+            Variable::Stat(side, stat) => Ok(stat.get(&self.nerds[side as usize]).into()),
+        }
+    }
+
+    // Writes an expression with its names replaced by their numbers
+    fn write(&mut self, expr: &Expr) -> Result<String, String> {
+        Ok(match expr {
+            Expr::Number(_) | Expr::Variable(_) | Expr::Named(_) => {
+                let num = self.eval(expr)?;
+                if num < 0 {
+                    format!("({num})")
+                } else {
+                    num.to_string()
+                }
+            }
+            Expr::Negative(expr) => format!("-{}", self.write(expr)?),
+            Expr::Binary(left, operator, right) => format!(
+                "{} {} {}",
+                self.write(left)?,
+                operator.symbol(),
+                self.write(right)?
+            ),
+            Expr::Brackets(expr) => format!("({})", self.write(expr)?),
+            Expr::Call(function, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.write(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!("{}({})", function.name(), args.join(", "))
+            }
+        })
+    }
+}
+
+// Returns a number kept inside of what stats and answers can be
+fn clamp(num: i64) -> i32 {
+    num.clamp(i32::MIN.into(), i32::MAX.into()) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nerds::NERDS;
+
+    // Runs a script's equation for the first action of the first roster nerd against itself
+    fn equation(text: &str) -> Result<(String, i32), String> {
+        let nerd = NERDS[0];
+        Script::parse(text)?.equation(&nerd, &nerd, 0, false)
+    }
+
+    #[test]
+    fn splits_expressions_into_tokens() {
+        assert_eq!(
+            tokenize("attacker.health*2 <= (x_1-10)").unwrap(),
+            [
+                "attacker.health",
+                "*",
+                "2",
+                "<=",
+                "(",
+                "x_1",
+                "-",
+                "10",
+                ")"
+            ]
+        );
+        assert_eq!(tokenize("a!=b==c").unwrap(), ["a", "!=", "b", "==", "c"]);
+        assert!(tokenize("1 & 2").is_err());
+        assert!(tokenize("1 ! 2").is_err());
+    }
+
+    #[test]
+    fn writes_and_works_out_equations() {
+        let nerd = NERDS[0];
+        let value = nerd.actions[0].value();
+        let (text, answer) =
+            equation("equation defender.health - value * attacker.multiplier").unwrap();
+        assert_eq!(
+            text,
+            format!("{} - {value} * {}", nerd.health, nerd.multiplier)
+        );
+        assert_eq!(answer, nerd.health - value * nerd.multiplier);
+        assert_eq!(equation("equation 1 + 2 * 3 == 7").unwrap().1, 1);
+        assert_eq!(equation("equation (1 + 2) * 3").unwrap().1, 9);
+        assert_eq!(equation("equation -7 % 3 - 10 / 4").unwrap().1, -3);
+        assert_eq!(
+            equation("equation min(4, 2) + max(4, 2) * abs(-3)").unwrap(),
+            ("min(4, 2) + max(4, 2) * abs(-3)".to_string(), 14)
+        );
+    }
+
+    #[test]
+    fn runs_lets_ifs_and_repeats() {
+        let text = "
+            let total = 0  # a comment
+            repeat 5
+                let total = total + 2
+            end
+            if total == 10
+                equation total
+            end
+            equation 0";
+        assert_eq!(equation(text).unwrap(), ("10".to_string(), 10));
+        assert_eq!(equation("if 0\nequation 1\nend\nequation 2").unwrap().1, 2);
+        assert_eq!(
+            equation("repeat -3\nequation 1\nend\nequation 2")
+                .unwrap()
+                .1,
+            2
+        );
+        assert_eq!(
+            equation("equation 9999999999 * 9999999999").unwrap().1,
+            i32::MAX
+        );
+    }
+
+    #[test]
+    fn applies_sets_and_logs_once_answered() {
+        let script = Script::parse(
+            "equation 1
+            set defender.health = answer
+            set attacker.multiplier = attacker.multiplier + value * crit
+            log Took {defender.health - answer} health",
+        )
+        .unwrap();
+        let (mut attacker, mut defender) = (NERDS[0], NERDS[1]);
+        let logs = script
+            .apply(&mut attacker, &mut defender, 0, true, 5)
+            .unwrap();
+        assert_eq!(defender.health, 5);
+        assert_eq!(
+            attacker.multiplier,
+            NERDS[0].multiplier + NERDS[0].actions[0].value() * NERDS[0].critical_multiplier
+        );
+        assert_eq!(logs, ["Took 0 health"]);
+    }
+
+    #[test]
+    fn stops_after_too_many_operations() {
+        let text = "let x = 0\nrepeat 5000000000\nlet x = x + 1\nend\nequation x";
+        let err = equation(text).unwrap_err();
+        assert!(err.contains(&MAX_OPERATIONS.to_string()), "{err}");
+        assert!(equation("let x = 0\nrepeat 100\nlet x = x + 1\nend\nequation x").is_ok());
+    }
+
+    #[test]
+    fn rejects_expressions_nested_too_deeply() {
+        let negatives = |count| format!("equation {}1", "-".repeat(count));
+        assert!(equation(&negatives(MAX_DEPTH - 1)).is_ok());
+        assert!(equation(&negatives(MAX_DEPTH))
+            .unwrap_err()
+            .contains("nested too deeply"));
+        let brackets = |count| format!("equation {}1{}", "(".repeat(count), ")".repeat(count));
+        assert!(equation(&brackets(MAX_DEPTH - 1)).is_ok());
+        assert!(equation(&brackets(MAX_DEPTH))
+            .unwrap_err()
+            .contains("nested too deeply"));
+        assert!(equation(&negatives(100_000)).is_err());
+    }
+
+    #[test]
+    fn rejects_blocks_nested_too_deeply() {
+        let blocks = |count| {
+            format!(
+                "{}equation 1\n{}",
+                "if 1\n".repeat(count),
+                "end\n".repeat(count)
+            )
+        };
+        assert!(equation(&blocks(MAX_DEPTH)).is_ok());
+        assert!(equation(&blocks(MAX_DEPTH + 1))
+            .unwrap_err()
+            .contains("nested too deeply"));
+    }
+
+    #[test]
+    fn rejects_expressions_with_too_many_tokens() {
+        let sum = |count| format!("equation 1{}", " + 1".repeat(count));
+        assert_eq!(
+            equation(&sum(MAX_TOKENS / 2 - 1)).unwrap().1 as usize,
+            MAX_TOKENS / 2
+        );
+        assert!(equation(&sum(MAX_TOKENS / 2))
+            .unwrap_err()
+            .contains("too long"));
+    }
+
+    #[test]
+    fn says_what_is_wrong_with_bad_syntax() {
+        for (text, err) in [
+            ("let = 1", "can't be used as a name"),
+            ("let value = 1", "can't be used as a name"),
+            ("let x 1", "let needs a name"),
+            ("set attacker.speed = 1", "can't be changed"),
+            ("set attacker.luck = 1", "isn't a stat"),
+            ("jump 1", "jump isn't a statement"),
+            ("end", "end doesn't close"),
+            ("if 1\nequation 1", "missing its end"),
+            ("equation 1 +", "ended too early"),
+            ("equation (1", ") is missing"),
+            ("equation 1 2", "2 wasn't expected"),
+            ("equation min(1)", "min takes 2 numbers"),
+            ("equation defender.luck", "isn't a stat"),
+            ("log {1", "missing its }"),
+        ] {
+            let result = Script::parse(text);
+            assert!(
+                result.as_ref().is_err_and(|other| other.contains(err)),
+                "{text}: expected {err}"
+            );
+        }
+        assert!(Script::parse("equation 1\n\njump")
+            .err()
+            .is_some_and(|err| err.starts_with("Line 3:")));
+    }
+
+    #[test]
+    fn says_what_went_wrong_while_running() {
+        for (text, err) in [
+            ("equation 1 / 0", "divided by 0"),
+            ("equation 1 % (2 - 2)", "divided by 0"),
+            ("equation x", "x was used before it was let"),
+            ("equation answer", "answer was used before"),
+            ("let x = 1", "no equation"),
+        ] {
+            assert!(equation(text).unwrap_err().contains(err), "{text}");
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::nerds::{ActionType, Nerd, Nerds};
+use std::collections::HashMap;
+
+// Health and multipliers of both nerds, whose turn it is, and how many turns are left to search
+type Position = (i32, i32, i32, i32, usize, usize);
+
+// How much less a win (or more a loss) counts for each turn it takes to happen, so faster wins and slower losses
+// are preferred between actions that would otherwise tie
+const TURN_PENALTY: f64 = 1e-6;
+
+// The best action for the nerd whose turn it is, found by searching the turns ahead
+// (assuming every answer is right and both nerds play as well as they can)
+pub struct Analysis {
+    pub action: usize,
+    pub win_chance: f64,
+    pub action_chances: Vec<f64>,
+}
+
+impl Analysis {
+    // Searches a number of turns ahead for the best action of the current nerd
+    pub fn new(nerds: &Nerds, current_nerd: usize, depth: usize) -> Self {
+        let depth = depth.max(1);
+        let mut search = Search {
+            memo: HashMap::new(),
+            depth,
+        };
+        let action_chances: Vec<f64> = (0..nerds[current_nerd].actions.len())
+            .map(|action| search.action_chance(nerds, current_nerd, action, depth))
+            .collect();
+        let (action, &win_chance) = action_chances
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .expect("nerds always have actions");
+        Self {
+            action,
+            win_chance,
+            action_chances,
+        }
+    }
+
+    // Returns the best action out of the ones that can be used, and its chance of winning
+    pub fn best_of(&self, usable: impl Fn(usize) -> bool) -> Option<(usize, f64)> {
+        self.action_chances
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(action, _)| usable(action))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+}
+
+// Expectiminimax search over the turns of a duel (critical hits are the chance part)
+struct Search {
+    memo: HashMap<Position, f64>,
+    depth: usize,
+}
+
+impl Search {
+    // Returns the chance of the current nerd winning if both nerds play their best
+    fn chance(&mut self, nerds: &Nerds, current_nerd: usize, depth: usize) -> f64 {
+        let other_nerd = 1 - current_nerd;
+        let (current, other) = (&nerds[current_nerd], &nerds[other_nerd]);
+        let penalty = (self.depth - depth) as f64 * TURN_PENALTY;
+        if current.health < 1 {
+            return penalty;
+        } else if other.health < 1 {
+            return 1.0 - penalty;
+        } else if depth == 0 {
+            let (turns, other_turns) = (
+                Self::turns_left(current, other),
+                Self::turns_left(other, current),
+            );
+            // Guesses from how many turns each nerd needs, counting it as a turn further away than any
+            // knockout the search can find (so a real knockout is always preferred)
+            return (other_turns / (turns + other_turns))
+                .clamp(penalty + TURN_PENALTY, 1.0 - penalty - TURN_PENALTY);
+        }
+
+        let position = (
+            current.health,
+            current.multiplier,
+            other.health,
+            other.multiplier,
+            current_nerd,
+            depth,
+        );
+        if let Some(&chance) = self.memo.get(&position) {
+            return chance;
+        }
+        let chance = (0..current.actions.len())
+            .map(|action| self.action_chance(nerds, current_nerd, action, depth))
+            .fold(0.0, f64::max);
+        self.memo.insert(position, chance);
+        chance
+    }
+
+    // Returns roughly how many turns a nerd needs to knock out another nerd with their strongest damage
+    fn turns_left(nerd: &Nerd, other: &Nerd) -> f64 {
+        let damage = nerd
+            .actions
+            .iter()
+            .filter(|action| action.action_type() == ActionType::Damage)
+            .map(|action| action.value() * nerd.multiplier)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        other.health as f64 / damage as f64
+    }
+
+    // Returns the chance of the current nerd winning after using an action (with or without a critical hit)
+    fn action_chance(
+        &mut self,
+        nerds: &Nerds,
+        current_nerd: usize,
+        action: usize,
+        depth: usize,
+    ) -> f64 {
+        let critical_chance = nerds[current_nerd].critical_chance as f64 / 100.0;
+        [(false, 1.0 - critical_chance), (true, critical_chance)]
+            .into_iter()
+            .map(|(critical, probability)| {
+                let next = after_action(nerds, current_nerd, action, critical);
+                probability * (1.0 - self.chance(&next, 1 - current_nerd, depth - 1))
+            })
+            .sum()
+    }
+}
+
+// Returns the nerds after the current nerd uses an action (unchanged if its script doesn't work)
+pub fn after_action(nerds: &Nerds, current_nerd: usize, action: usize, critical: bool) -> Nerds {
+    let mut nerds = *nerds;
+    let other_nerd = 1 - current_nerd;
+    let Ok(equation) = nerds[current_nerd].equation(action, &nerds[other_nerd], critical) else {
+        return nerds;
+    };
+    let answer = equation.answer();
+    let mut other = nerds[other_nerd];
+    nerds[current_nerd].apply_action(action, answer, critical, &mut other);
+    nerds[other_nerd] = other;
+    nerds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nerds::NERDS;
+
+    // Returns the roster nerd with a name
+    fn nerd(name: &str) -> Nerd {
+        NERDS
+            .into_iter()
+            .find(|nerd| nerd.name == name)
+            .expect("the nerd is on the roster")
+    }
+
+    #[test]
+    fn recommends_an_immediate_knockout() {
+        let nerds = [nerd("Yamin"), nerd("Joe")];
+        let damage = 0;
+        assert!(after_action(&nerds, 0, damage, false)[1].health < 1);
+        for depth in 1..=6 {
+            assert_eq!(Analysis::new(&nerds, 0, depth).action, damage);
+        }
+    }
+}
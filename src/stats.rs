@@ -0,0 +1,60 @@
+use crate::equation::{Mistake, MISTAKES};
+use crate::storage::Save;
+
+// Name of the file the stats are saved in
+const SAVE_NAME: &str = "stats";
+
+// How the players have answered multiple choice equations (so teachers can see which mistakes are made)
+pub struct Stats {
+    right: usize,
+    mistakes: [usize; MISTAKES.len()],
+}
+
+impl Stats {
+    // Loads the saved stats, otherwise nothing has been answered
+    pub fn load() -> Self {
+        let save = Save::load(SAVE_NAME).unwrap_or_default();
+        Self {
+            right: save.get_parsed("right").unwrap_or(0),
+            mistakes: MISTAKES.map(|mistake| save.get_parsed(mistake.save_name()).unwrap_or(0)),
+        }
+    }
+
+    // Saves the stats
+    fn save(&self) {
+        let mut save = Save::new();
+        save.set("right", self.right);
+        for (mistake, count) in MISTAKES.iter().zip(self.mistakes) {
+            save.set(mistake.save_name(), count);
+        }
+        let _ = save.write(SAVE_NAME);
+    }
+
+    // Records a multiple choice answer, with the mistake it came from if it was wrong
+    pub fn record(&mut self, mistake: Option<Mistake>) {
+        match mistake {
+            Some(mistake) => {
+                if let Some(i) = MISTAKES.iter().position(|&other| other == mistake) {
+                    self.mistakes[i] += 1;
+                }
+            }
+            None => self.right += 1,
+        }
+        self.save();
+    }
+
+    // Returns the lines shown on the stats screen
+    pub fn lines(&self) -> Vec<String> {
+        let wrong: usize = self.mistakes.iter().sum();
+        let mut lines = vec![
+            "Multiple choice answers".to_string(),
+            format!("Right: {} of {}", self.right, self.right + wrong),
+            String::new(),
+            "Wrong answers picked:".to_string(),
+        ];
+        for (mistake, count) in MISTAKES.iter().zip(self.mistakes) {
+            lines.push(format!("{}: {count}", mistake.name()));
+        }
+        lines
+    }
+}
@@ -0,0 +1,93 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// Folder (inside the home folder) where everything is saved
+const SAVE_DIR: &str = ".acnd";
+const SAVE_EXTENSION: &str = "txt";
+
+// Separates the key and value of a line in a save file
+const SEPARATOR: &str = " = ";
+
+// Key value pairs that are saved to (and loaded from) a file
+#[derive(Default)]
+pub struct Save {
+    entries: Vec<(String, String)>,
+}
+
+impl Save {
+    // Creates an empty save
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Loads a save from its file if it exists
+    pub fn load(name: &str) -> Option<Self> {
+        let text = fs::read_to_string(Self::path(name)).ok()?;
+        let entries = text
+            .lines()
+            .filter_map(|line| line.split_once(SEPARATOR))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        Some(Self { entries })
+    }
+
+    // Writes the save to its file
+    pub fn write(&self, name: &str) -> io::Result<()> {
+        let path = Self::path(name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text: String = self
+            .entries
+            .iter()
+            .map(|(key, value)| format!("{key}{SEPARATOR}{value}\n"))
+            .collect();
+        fs::write(path, text)
+    }
+
+    // Deletes the file of a save
+    pub fn remove(name: &str) -> io::Result<()> {
+        fs::remove_file(Self::path(name))
+    }
+
+    // Adds a value to the save (keys can be repeated for lists)
+    pub fn set<T: ToString>(&mut self, key: &str, value: T) {
+        let value = value.to_string().replace('\n', " ");
+        self.entries.push((key.to_string(), value));
+    }
+
+    // Returns the first value with the key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Returns the first value with the key parsed into another type
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key)?.parse().ok()
+    }
+
+    // Returns every value with the key in order
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Returns the path of a save file
+    fn path(name: &str) -> PathBuf {
+        path(name, SAVE_EXTENSION)
+    }
+}
+
+// Returns the path of a file in the save folder
+pub fn path(name: &str, extension: &str) -> PathBuf {
+    let home = env::var_os("HOME").map_or_else(|| PathBuf::from("."), PathBuf::from);
+    home.join(SAVE_DIR).join(name).with_extension(extension)
+}
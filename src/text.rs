@@ -0,0 +1,271 @@
+use crate::creator;
+use crate::duel::Duel;
+use crate::game::{self, GAME_END_MESSAGE, HINT_DEPTH, LOCKED_ACTION_MESSAGE};
+use crate::nerds::{Nerd, Nerds};
+use crate::options::Options;
+use crate::solver::Analysis;
+use crate::stats::Stats;
+use crate::unlocks::{self, Unlockable, Unlocks};
+use std::io::{self, Lines, StdinLock};
+
+// Commands that can be typed instead of a number
+const QUIT_COMMANDS: [&str; 2] = ["quit", "q"];
+const HINT_COMMANDS: [&str; 2] = ["hint", "h"];
+const TAUNT_COMMANDS: [&str; 2] = ["taunt", "t"];
+const BACK_COMMANDS: [&str; 2] = ["back", "b"];
+
+// Shown when the game starts
+const INTRO_TEXT: &str = "AC Nerd Duels (line mode)
+Type a number and press enter to choose, or type quit at any time to quit.";
+
+// Plain frontend that prints what happens as lines and reads commands one line at a time
+// (no ASCII art or cursor movement, so it works with pipes and screen readers)
+pub struct TextGame {
+    lines: Lines<StdinLock<'static>>,
+    roster: Vec<Nerd>,
+    options: Options,
+    unlocks: Unlocks,
+    stats: Stats,
+    last_loser: Option<usize>,
+}
+
+impl TextGame {
+    // Creates a game that reads from stdin and prints to stdout
+    pub fn new() -> Self {
+        Self {
+            lines: io::stdin().lines(),
+            roster: creator::roster(),
+            options: Options::load(),
+            unlocks: Unlocks::load(),
+            stats: Stats::load(),
+            last_loser: None,
+        }
+    }
+
+    // Plays versus matches until the input ends or the players quit
+    pub fn main_loop(&mut self) {
+        println!("{INTRO_TEXT}");
+        while self.play_match().is_some() {
+            println!("Play again? (y/n)");
+            if !matches!(self.read_line().as_deref(), Some("y" | "yes")) {
+                break;
+            }
+        }
+        println!("Bye!");
+    }
+
+    // Plays one match from choosing the nerds to the end; returns none if the players quit
+    fn play_match(&mut self) -> Option<()> {
+        let nerds = self.choose_nerds()?;
+        let mut duel = self.start_duel(nerds);
+        let mut printed = 0;
+        loop {
+            if let Some(winner) = duel.winner() {
+                self.finish_duel(&mut duel, winner);
+                Self::print_messages(&duel, &mut printed);
+                return Some(());
+            }
+            Self::print_messages(&duel, &mut printed);
+            Self::print_turn(&duel, self.options.reveal_critical, &self.unlocks);
+            self.take_turn(&mut duel)?;
+        }
+    }
+
+    // Has both players choose an unlocked nerd (created nerds included)
+    fn choose_nerds(&mut self) -> Option<Nerds> {
+        let nerds: Vec<Nerd> = self
+            .roster
+            .iter()
+            .copied()
+            .filter(|nerd| self.unlocks.is_unlocked(Unlockable::Nerd(nerd.name)))
+            .collect();
+        println!("Nerds:");
+        for (i, nerd) in nerds.iter().enumerate() {
+            println!(
+                "{}. {} ({}, {} health, speed {}): {}",
+                i + 1,
+                nerd.name,
+                nerd.subject.name(),
+                nerd.max_health,
+                nerd.speed,
+                nerd.bio
+            );
+        }
+        let first = nerds[self.choose(&nerds, "Player 1, choose your nerd")?];
+        loop {
+            let second = nerds[self.choose(&nerds, "Player 2, choose your nerd")?];
+            if first.name != second.name || self.options.mirror_matches {
+                return Some([first, second]);
+            }
+            println!("Mirror matches are turned off in the options");
+        }
+    }
+
+    // Asks for a number from 1 to the number of choices until one is given, and returns its index
+    fn choose<T>(&mut self, choices: &[T], prompt: &str) -> Option<usize> {
+        loop {
+            println!("{prompt} (1-{}):", choices.len());
+            if let Some(i) = Self::parse_choice(&self.read_line()?, choices.len()) {
+                return Some(i);
+            }
+        }
+    }
+
+    // Starts a duel between two nerds with the rules in the options
+    fn start_duel(&self, nerds: Nerds) -> Duel {
+        Duel::start(nerds, &self.options, self.last_loser)
+    }
+
+    // Announces the winner and unlocks what the win unlocks
+    fn finish_duel(&mut self, duel: &mut Duel, winner: usize) {
+        let names = [duel.nerds[winner].name, duel.nerds[1 - winner].name];
+        duel.add_message(&game::fill_names(GAME_END_MESSAGE, names));
+        self.last_loser = Some(1 - winner);
+        let mut unlocked = self.unlocks.add_win();
+        if duel.nerds[winner].health >= duel.starting_nerds()[winner].health {
+            unlocked.extend(self.unlocks.achieve(unlocks::FLAWLESS));
+        }
+        for unlockable in unlocked {
+            duel.add_message(&format!("Unlocked: {}", unlockable.name()));
+        }
+    }
+
+    // Has the current player choose an action (or a hint or taunt) and answer its equation
+    fn take_turn(&mut self, duel: &mut Duel) -> Option<()> {
+        let nerd = duel.nerds[duel.current_nerd];
+        let usable: Vec<bool> = nerd
+            .actions
+            .iter()
+            .map(|action| {
+                self.unlocks
+                    .is_unlocked(Unlockable::Action(action.base_name()))
+            })
+            .collect();
+        let mut prompt = format!("Choose an action (1-{}), or hint", nerd.actions.len());
+        if duel.can_taunt(duel.current_nerd) {
+            prompt += " or taunt";
+        }
+        println!("{prompt}:");
+        let line = self.read_line()?;
+        if HINT_COMMANDS.contains(&line.as_str()) {
+            let analysis = Analysis::new(&duel.nerds, duel.current_nerd, HINT_DEPTH);
+            if let Some((action, win_chance)) = analysis.best_of(|action| usable[action]) {
+                println!(
+                    "Hint: {} ({:.0}% chance of winning)",
+                    nerd.actions[action].name(),
+                    win_chance * 100.0
+                );
+            }
+        } else if TAUNT_COMMANDS.contains(&line.as_str()) && duel.can_taunt(duel.current_nerd) {
+            for (i, taunt) in nerd.taunts.iter().enumerate() {
+                println!("{}. {taunt}", i + 1);
+            }
+            println!("Choose a taunt (1-{}), or back:", nerd.taunts.len());
+            let line = self.read_line()?;
+            if let Some(taunt) = Self::parse_choice(&line, nerd.taunts.len()) {
+                duel.taunt(duel.current_nerd, taunt);
+            }
+        } else if let Some(action) = Self::parse_choice(&line, nerd.actions.len()) {
+            if !usable[action] {
+                println!("{LOCKED_ACTION_MESSAGE}");
+                return Some(());
+            }
+            if !duel.choose_action(action) {
+                return Some(());
+            }
+            if duel.missing() {
+                println!("{} (what is ?)", duel.equation());
+            } else {
+                println!("{} = ?", duel.equation());
+            }
+            let choices = duel.choices();
+            for (i, choice) in choices.iter().enumerate() {
+                println!("{}. {choice}", i + 1);
+            }
+            loop {
+                if choices.is_empty() {
+                    println!(
+                        "Type the {}, or back:",
+                        if duel.missing() {
+                            "missing number"
+                        } else {
+                            "answer"
+                        }
+                    );
+                } else {
+                    println!("Choose an answer (1-{}), or back:", choices.len());
+                }
+                let line = self.read_line()?;
+                if BACK_COMMANDS.contains(&line.as_str()) {
+                    break;
+                } else if !choices.is_empty() {
+                    if let Some(choice) = Self::parse_choice(&line, choices.len()) {
+                        self.stats.record(duel.mistake(choices[choice]));
+                        duel.submit(choices[choice]);
+                        break;
+                    }
+                } else if let Ok(num) = line.parse() {
+                    duel.submit(num);
+                    break;
+                }
+            }
+        }
+        Some(())
+    }
+
+    // Prints the stats of the nerds, whose turn it is and the current nerd's actions
+    fn print_turn(duel: &Duel, reveal_critical: bool, unlocks: &Unlocks) {
+        for (nerd, difficulty) in duel.nerds.iter().zip(&duel.difficulties) {
+            println!(
+                "{}: Health = {}/{}, Multiplier = {}, Level = {} ({})",
+                nerd.name,
+                nerd.health,
+                nerd.max_health,
+                nerd.multiplier,
+                difficulty.level(),
+                difficulty.name()
+            );
+        }
+        let (nerd, other) = (
+            &duel.nerds[duel.current_nerd],
+            &duel.nerds[duel.other_nerd()],
+        );
+        println!("It's {}'s turn", nerd.name);
+        if reveal_critical && duel.critical() {
+            println!("Critical hit ready!");
+        }
+        for (i, action) in nerd.actions.iter().enumerate() {
+            let mut name = action.name();
+            let effectiveness = nerd.effectiveness(i, other);
+            if effectiveness > 1 {
+                name += &format!(" x{effectiveness}!");
+            }
+            if !unlocks.is_unlocked(Unlockable::Action(action.base_name())) {
+                name += " (locked)";
+            }
+            println!("{}. {name}", i + 1);
+        }
+    }
+
+    // Prints the messages of the duel that haven't been printed yet
+    fn print_messages(duel: &Duel, printed: &mut usize) {
+        for message in &duel.messages()[*printed..] {
+            println!("{message}");
+        }
+        *printed = duel.messages().len();
+    }
+
+    // Returns the index of a choice typed as a number from 1 to the number of choices
+    fn parse_choice(line: &str, choices: usize) -> Option<usize> {
+        line.parse::<usize>()
+            .ok()
+            .filter(|choice| (1..=choices).contains(choice))
+            .map(|choice| choice - 1)
+    }
+
+    // Reads the next line (trimmed and lowercased); returns none when the input ends or quit is typed
+    fn read_line(&mut self) -> Option<String> {
+        let line = self.lines.next()?.ok()?.trim().to_lowercase();
+        (!QUIT_COMMANDS.contains(&line.as_str())).then_some(line)
+    }
+}
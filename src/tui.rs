@@ -1,6 +1,11 @@
-use crate::game::{GameState, InGameState};
-use crate::nerds::{Nerd, Nerds, CURRENT_NERD_COLOR, NERDS, WAITING_NERD_COLOR};
-use console_engine::{Color, ConsoleEngine, KeyCode};
+use crate::duel::Duel;
+use crate::equation::Difficulty;
+use crate::game::{GameMode, GameState, InGameState, PracticeState};
+use crate::nerds::{Nerd, Nerds, CURRENT_NERD_COLOR, WAITING_NERD_COLOR};
+use crate::options::SelectionMode;
+use crate::practice::{self, PracticeSettings, DIFFICULTIES, LENGTHS, OPERATIONS};
+use console_engine::events::Event;
+use console_engine::{pixel, Color, ConsoleEngine, KeyCode, KeyEventKind, KeyModifiers};
 use euclid::{Point2D, UnknownUnit};
 
 // Console engine initialization
@@ -12,6 +17,10 @@ const FPS: i32 = 60;
 const QUIT_KEY: KeyCode = KeyCode::Char('q');
 const BACK_KEY: KeyCode = KeyCode::Esc;
 const START_KEY: KeyCode = KeyCode::Enter;
+const PAUSE_KEY: KeyCode = KeyCode::Char('p');
+const RANDOM_KEY: KeyCode = KeyCode::Char('r');
+const HINT_KEY: KeyCode = KeyCode::Char('h');
+const TAUNT_KEY: KeyCode = KeyCode::Char('t');
 const UP_KEY: KeyCode = KeyCode::Up;
 const DOWN_KEY: KeyCode = KeyCode::Down;
 const LEFT_KEY: KeyCode = KeyCode::Left;
@@ -42,12 +51,75 @@ const LOGO_TEXT: &str = "  ___  _____  _   _______
 | | | | \\__/\\| |\\  | |/ /
 \\_| |_/\\____/\\_| \\_/___/";
 const LOGO_COLOR: Color = Color::Blue;
-const QUIT_TEXT: &str = "Use the arrow keys to select something, and 'q' to quit at any time";
+const QUIT_TEXT: &str =
+    "Use the arrow keys to select something, 'p' to pause a game, and 'q' to quit at any time";
 const SELECT_TEXTS: [&str; 2] = ["Nerd 1: ", "Nerd 2: "];
 const SELECT_COLOR: Color = Color::Magenta;
 const START_TEXT: &str = "Press the enter/return key to start the game or skip the intro";
+const MENU_COLOR: Color = Color::Cyan;
+const CAMPAIGN_SELECT_TEXT: &str = "Your nerd: ";
+const BACK_TEXT: &str = "Press escape to go back";
+
+// Stuff shown in the different ways of selecting nerds
+const RANDOM_TEXT: &str = "Press 'r' to be surprised with a random nerd";
+const BAN_TEXTS: [&str; 2] = ["Nerd 1, ban a nerd", "Nerd 2, ban a nerd"];
+const PICK_TEXTS: [&str; 2] = ["Nerd 1, pick a nerd", "Nerd 2, pick a nerd"];
+const BLIND_PICK_TEXTS: [&str; 2] = [
+    "Nerd 1, pick a nerd (Nerd 2, look away!)",
+    "Nerd 2, pick a nerd (Nerd 1, look away!)",
+];
+const BANNED_TEXT: &str = "Banned: ";
+const HIDDEN_TEXT: &str = "???";
+const MIRROR_TEXT: &str = "Both players can't pick the same nerd";
+const BLIND_MIRROR_TEXT: &str = "Both players picked the same nerd, so pick again";
+const WARNING_COLOR: Color = Color::Red;
+
+// Stuff shown about the nerds being selected
+const INFO_WIDTH: usize = 50;
+const MAX_BIO_LINES: usize = 2;
+const BETTER_COLOR: Color = Color::Green;
+const WORSE_COLOR: Color = Color::Red;
+
+// Stuff shown when setting up practice
+const PRACTICE_TITLE: &str = "Practice";
+const PRACTICE_SETTING_TEXTS: [&str; 3] = ["Operation: ", "Difficulty: ", "Length: "];
+
+// Stuff shown in the pause and options menus
+const PAUSE_TITLE: &str = "Paused";
+const PAUSE_WIDTH: i32 = 40;
+const OPTIONS_TITLE: &str = "Options";
+const OPTIONS_TEXT: &str = "Use the left and right arrow keys to change an option";
+
+// Stuff shown in the nerd creator
+const CREATOR_TITLE: &str = "Create a Nerd";
+const CREATOR_TEXTS: [&str; 2] = [
+    "Type to change text, use the left and right arrow keys to change values, and press enter on Save",
+    "A sprite can be typed a line at a time, or loaded by typing the path of a text file and pressing enter",
+];
+const PREVIEW_POSITION: [i32; 2] = [4, 3];
+const TYPEABLE_CHARS: std::ops::RangeInclusive<char> = ' '..='~';
+
+// Stuff shown in dialogue and after games
+const CONTINUE_TEXT: &str = "Press the enter/return key to continue";
+const DIALOGUE_COLOR: Color = Color::Yellow;
 
 // Stuff used for displaying stuff related to the game
+const HINT_TEXT: &str = "Press 'h' for a hint";
+const TURN_ORDER_ROW: i32 = 3;
+const HINT_COLOR: Color = Color::Yellow;
+const LOCKED_ACTION_COLOR: Color = Color::DarkGrey;
+const CRITICAL_READY_TEXT: &str = "Critical hit ready!";
+const CRITICAL_TEXT: &str = " CRITICAL HIT!!! ";
+const CRITICAL_COLORS: [Color; 2] = [Color::Yellow, Color::Red];
+const FLASH_FRAMES: usize = 40;
+const FLASH_BLINK_FRAMES: usize = 5;
+const TAUNT_TEXT: &str = "Press 't' to taunt";
+const TAUNTS_TITLE: &str = "Taunts (escape to go back):";
+const CHOICES_TITLE: &str = "Answers (escape to go back):";
+const CHOICES_TEXT: &str = "Press a number or enter to answer";
+const BUBBLE_WIDTH: usize = 24;
+const BUBBLE_COLOR: Color = Color::White;
+const NERD_POSITIONS: [i32; 2] = [-50, 10];
 const MAX_ACTION_MESSAGES: usize = 5;
 const HORIZONTAL_DIVIDER: &str = "-";
 const ACTION_LIST_WIDTH: usize = 35;
@@ -59,17 +131,53 @@ const ENGINE_FAIL_ERR_MSG: &str = "Console Engine failed to start";
 // Represents a point on the screen
 type Point = Point2D<i32, UnknownUnit>;
 
+// Keys pressed since the last frame, in the order they were pressed (with their modifiers)
+struct Keys(Vec<(KeyCode, KeyModifiers)>);
+
+impl Keys {
+    // Returns whether a key was pressed without any modifiers
+    fn is_pressed(&self, key: KeyCode) -> bool {
+        self.0.contains(&(key, KeyModifiers::NONE))
+    }
+}
+
 // Manages the terminal, and whats displayed and inputted
 pub struct Tui {
     engine: ConsoleEngine,
+    keys: Keys,
     width: i32,
     height: i32,
+    menu: Vec<String>,
+    current_menu_selection: usize,
     current_nerd_selection: usize,
     nerd_selects: [usize; 2],
+    roster: Vec<Nerd>,
+    selectable: Vec<usize>,
+    selection_mode: SelectionMode,
+    allow_mirror: bool,
+    select_phase: SelectPhase,
+    bans: Vec<usize>,
+    mirror_warning: Option<&'static str>,
     secret_index: usize,
-    action_messages: Vec<String>,
+    secret_entered: bool,
     current_action_selection: usize,
+    current_taunt_selection: usize,
     inputted_math: String,
+    choices: Vec<i32>,
+    current_choice_selection: usize,
+    choice_pressed: Option<usize>,
+    ais: [bool; 2],
+    locked_actions: Vec<&'static str>,
+    dialogue: Vec<String>,
+    dialogue_line: usize,
+    current_practice_selection: usize,
+    practice_selects: [usize; 3],
+    info: Vec<String>,
+    hint: Vec<String>,
+    preview: String,
+    typing: bool,
+    reveal_critical: bool,
+    flash_frames: usize,
 }
 
 impl Tui {
@@ -82,78 +190,343 @@ impl Tui {
         let height = engine.get_height() as i32;
         Self {
             engine,
+            keys: Keys(Vec::new()),
             width,
             height,
+            menu: Vec::new(),
+            current_menu_selection: 0,
             current_nerd_selection: 0,
             nerd_selects: [0, 0],
+            roster: Vec::new(),
+            selectable: Vec::new(),
+            selection_mode: SelectionMode::Standard,
+            allow_mirror: true,
+            select_phase: SelectPhase::Both,
+            bans: Vec::new(),
+            mirror_warning: None,
             secret_index: 0,
-            action_messages: Vec::new(),
+            secret_entered: false,
             current_action_selection: 0,
+            current_taunt_selection: 0,
             inputted_math: String::new(),
+            choices: Vec::new(),
+            current_choice_selection: 0,
+            choice_pressed: None,
+            ais: [false, false],
+            locked_actions: Vec::new(),
+            dialogue: Vec::new(),
+            dialogue_line: 0,
+            current_practice_selection: 0,
+            practice_selects: [0, 0, 0],
+            info: Vec::new(),
+            hint: Vec::new(),
+            preview: String::new(),
+            typing: false,
+            reveal_critical: false,
+            flash_frames: 0,
         }
     }
 
     // Updates the TUI
-    pub fn update(
-        &mut self,
-        game_state: GameState,
-        nerds: &Option<Nerds>,
-        current_nerd: usize,
-        equation: &str,
-    ) {
-        self.draw_and_input(game_state, nerds, current_nerd, equation);
+    pub fn update(&mut self, game_state: GameState, duel: Option<&Duel>, equation: &str) {
+        self.draw_and_input(game_state, duel, equation);
         self.engine.draw();
         self.engine.clear_screen();
-        self.engine.wait_frame();
+        self.wait_frame();
+    }
+
+    // Waits for the next frame, keeping the keys pressed until then in the order they were pressed
+    // (so typed text isn't reordered, and a key pressed twice counts twice)
+    fn wait_frame(&mut self) {
+        self.keys.0.clear();
+        loop {
+            match self.engine.poll() {
+                Event::Frame => return,
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    self.keys.0.push((key.code, key.modifiers));
+                }
+                _ => {}
+            }
+        }
     }
 
-    // Returns whether the player wants to quit
+    // Returns whether the player wants to quit (which can't be done while typing)
     pub fn should_quit(&self) -> bool {
-        self.engine.is_key_pressed(QUIT_KEY)
+        !self.typing && self.keys.is_pressed(QUIT_KEY)
     }
 
     // Returns whether the intro is done
     pub fn intro_done(&self) -> bool {
-        self.engine.frame_count as i32 / FPS >= INTRO_TIME * 2
-            || self.engine.is_key_pressed(START_KEY)
+        self.engine.frame_count as i32 / FPS >= INTRO_TIME * 2 || self.keys.is_pressed(START_KEY)
     }
 
-    // Returns the chosen nerds if the game has started
-    pub fn nerds_chosen(&self) -> Option<Nerds> {
-        if self.engine.is_key_pressed(START_KEY) {
-            return Some([NERDS[self.nerd_selects[0]], NERDS[self.nerd_selects[1]]]);
+    // Returns whether the player wants to move on
+    pub fn confirmed(&self) -> bool {
+        self.keys.is_pressed(START_KEY)
+    }
+
+    // Returns whether the player wants to pause or unpause
+    pub fn pause_pressed(&self) -> bool {
+        self.keys.is_pressed(PAUSE_KEY)
+    }
+
+    // Shows a new list of items in a menu with the first one selected
+    pub fn open_menu(&mut self, items: Vec<String>) {
+        self.current_menu_selection = 0;
+        self.menu = items;
+    }
+
+    // Changes the text of the items in the current menu
+    pub fn set_menu_items(&mut self, items: Vec<String>) {
+        self.current_menu_selection = self
+            .current_menu_selection
+            .min(items.len().saturating_sub(1));
+        self.menu = items;
+    }
+
+    // Returns the index of the chosen menu item (if one is chosen)
+    pub fn menu_chosen(&self) -> Option<usize> {
+        if self.keys.is_pressed(START_KEY) && !self.menu.is_empty() {
+            return Some(self.current_menu_selection);
         }
         None
     }
 
-    // Adds a new message to be displayed; cuts off messages that aren't shown
-    pub fn add_action_message(&mut self, text: &str) {
-        self.action_messages.push(text.to_string());
-        if self.action_messages.len() > MAX_ACTION_MESSAGES {
-            self.action_messages.remove(0);
+    // Returns the index of the selected menu item and the text typed into it (if any was typed)
+    pub fn text_typed(&self) -> Option<(usize, String)> {
+        let text: String = self
+            .keys
+            .0
+            .iter()
+            .filter_map(|&(key, modifiers)| match key {
+                KeyCode::Char(c)
+                    if TYPEABLE_CHARS.contains(&c)
+                        && (modifiers - KeyModifiers::SHIFT).is_empty() =>
+                {
+                    Some(c)
+                }
+                _ => None,
+            })
+            .collect();
+        (!text.is_empty()).then_some((self.current_menu_selection, text))
+    }
+
+    // Returns the index of the selected menu item if backspace was pressed
+    pub fn erase_pressed(&self) -> Option<usize> {
+        self.engine
+            .is_key_pressed(KeyCode::Backspace)
+            .then_some(self.current_menu_selection)
+    }
+
+    // Sets the sprite shown beside the nerd creator
+    pub fn set_preview(&mut self, sprite: &str) {
+        self.preview = sprite.to_string();
+    }
+
+    // Returns the index of the menu item being changed and whether it's going forward
+    pub fn menu_changed(&self) -> Option<(usize, bool)> {
+        if self.keys.is_pressed(LEFT_KEY) {
+            Some((self.current_menu_selection, false))
+        } else if self.keys.is_pressed(RIGHT_KEY) {
+            Some((self.current_menu_selection, true))
+        } else {
+            None
         }
     }
 
+    // Starts selecting from the unlocked nerds of a roster in a way, and whether both players can pick the same nerd
+    pub fn start_nerd_select(
+        &mut self,
+        roster: Vec<Nerd>,
+        selectable: Vec<usize>,
+        mode: SelectionMode,
+        allow_mirror: bool,
+    ) {
+        self.roster = roster;
+        self.selectable = selectable;
+        self.selection_mode = mode;
+        self.allow_mirror = allow_mirror;
+        self.select_phase = match mode {
+            SelectionMode::Standard => SelectPhase::Both,
+            SelectionMode::Blind => SelectPhase::Pick(0),
+            SelectionMode::Draft => SelectPhase::Ban(0),
+        };
+        self.current_nerd_selection = 0;
+        self.bans.clear();
+        self.mirror_warning = None;
+        self.fix_nerd_selects();
+    }
+
+    // Returns whether the secret sequence was just entered
+    pub fn secret_entered(&mut self) -> bool {
+        std::mem::take(&mut self.secret_entered)
+    }
+
+    // Makes a roster nerd selectable and selects it for the player currently selecting
+    pub fn select_nerd(&mut self, nerd: usize) {
+        if !self.selectable.contains(&nerd) {
+            self.selectable.push(nerd);
+            self.selectable.sort_unstable();
+        }
+        if self
+            .available_nerds(self.current_nerd_selection)
+            .contains(&nerd)
+        {
+            self.nerd_selects[self.current_nerd_selection] = nerd;
+        }
+    }
+
+    // Moves on to the next ban or pick if wanted; returns the chosen nerds once every pick is made
+    pub fn nerds_chosen(&mut self) -> Option<Nerds> {
+        if !self.keys.is_pressed(START_KEY) {
+            return None;
+        }
+        let nerds = [
+            self.roster[self.nerd_selects[0]],
+            self.roster[self.nerd_selects[1]],
+        ];
+        let mirror = !self.allow_mirror && self.nerd_selects[0] == self.nerd_selects[1];
+        match self.select_phase {
+            // Blind picks are only compared once both are in, so the warning can't give away the first pick
+            SelectPhase::Pick(1) if mirror && self.selection_mode == SelectionMode::Blind => {
+                self.mirror_warning = Some(BLIND_MIRROR_TEXT);
+                self.select_phase = SelectPhase::Pick(0);
+            }
+            SelectPhase::Both | SelectPhase::Pick(1) if mirror => {
+                self.mirror_warning = Some(MIRROR_TEXT);
+                return None;
+            }
+            SelectPhase::Both => return Some(nerds),
+            SelectPhase::Ban(player) => {
+                self.bans.push(self.nerd_selects[player]);
+                self.select_phase = if player == 0 {
+                    SelectPhase::Ban(1)
+                } else {
+                    SelectPhase::Pick(0)
+                };
+            }
+            SelectPhase::Pick(0) => self.select_phase = SelectPhase::Pick(1),
+            SelectPhase::Pick(_) => return Some(nerds),
+        }
+        if let SelectPhase::Ban(player) | SelectPhase::Pick(player) = self.select_phase {
+            self.current_nerd_selection = player;
+        }
+        self.fix_nerd_selects();
+        None
+    }
+
+    // Returns the chosen practice settings if practice has started
+    pub fn practice_settings_chosen(&self) -> Option<PracticeSettings> {
+        if self.keys.is_pressed(START_KEY) {
+            return Some(PracticeSettings {
+                operation: OPERATIONS[self.practice_selects[0]],
+                difficulty: DIFFICULTIES[self.practice_selects[1]],
+                length: LENGTHS[self.practice_selects[2]],
+            });
+        }
+        None
+    }
+
+    // Sets the lines of information shown in the middle of the screen
+    pub fn set_info(&mut self, lines: Vec<String>) {
+        self.info = lines;
+    }
+
+    // Returns whether the player wants a hint
+    pub fn hint_pressed(&self) -> bool {
+        self.keys.is_pressed(HINT_KEY)
+    }
+
+    // Sets the lines of the hint shown under the actions (none hides it)
+    pub fn set_hint(&mut self, lines: Vec<String>) {
+        self.hint = lines;
+    }
+
+    // Sets whether critical hits are shown before an action is chosen
+    pub fn set_reveal_critical(&mut self, reveal: bool) {
+        self.reveal_critical = reveal;
+    }
+
+    // Flashes the screen to show a critical hit just happened
+    pub fn flash_critical(&mut self) {
+        self.flash_frames = FLASH_FRAMES;
+    }
+
+    // Sets which nerds are controlled by the AI (players can't input for them)
+    pub fn set_ais(&mut self, ais: [bool; 2]) {
+        self.ais = ais;
+    }
+
+    // Sets the names of the actions players can't use yet (they're greyed out in the action list)
+    pub fn set_locked_actions(&mut self, names: Vec<&'static str>) {
+        self.locked_actions = names;
+    }
+
+    // Starts showing lines of dialogue one at a time
+    pub fn start_dialogue(&mut self, lines: Vec<String>) {
+        self.dialogue = lines;
+        self.dialogue_line = 0;
+    }
+
+    // Moves on to the next line of dialogue if wanted; returns whether every line has been read
+    pub fn dialogue_done(&mut self) -> bool {
+        if self.keys.is_pressed(START_KEY) {
+            self.dialogue_line += 1;
+        }
+        self.dialogue_line >= self.dialogue.len()
+    }
+
     // Returns the chosen action (if one is chosen)
     pub fn action_chosen(&self) -> Option<usize> {
-        if self.engine.is_key_pressed(START_KEY) {
+        if self.keys.is_pressed(START_KEY) {
             return Some(self.current_action_selection);
         }
         None
     }
 
+    // Returns whether the player wants to taunt
+    pub fn taunt_pressed(&self) -> bool {
+        self.keys.is_pressed(TAUNT_KEY)
+    }
+
+    // Returns the chosen taunt (if one is chosen)
+    pub fn taunt_chosen(&mut self) -> Option<usize> {
+        if self.keys.is_pressed(START_KEY) {
+            let taunt = self.current_taunt_selection;
+            self.current_taunt_selection = 0;
+            return Some(taunt);
+        }
+        None
+    }
+
     // Returns whether the player wants to go back to action selection
     pub fn back(&mut self) -> bool {
-        if self.engine.is_key_pressed(BACK_KEY) {
+        if self.keys.is_pressed(BACK_KEY) {
             self.inputted_math = String::new();
+            self.current_choice_selection = 0;
             return true;
         }
         false
     }
 
-    // Returns the inputted math number if it was entered
+    // Returns the inputted math number (or the chosen answer if answers are multiple choice) if it was entered
     pub fn math_chosen(&mut self) -> Option<i32> {
-        if self.engine.is_key_pressed(START_KEY) {
+        if !self.choices.is_empty() {
+            let choice = self
+                .choice_pressed
+                .take()
+                .or_else(|| {
+                    self.engine
+                        .is_key_pressed(START_KEY)
+                        .then_some(self.current_choice_selection)
+                })
+                .and_then(|choice| self.choices.get(choice).copied());
+            if choice.is_some() {
+                self.current_choice_selection = 0;
+            }
+            return choice;
+        }
+        if self.keys.is_pressed(START_KEY) {
             let num = self.inputted_math.parse();
             if let Ok(num) = num {
                 self.inputted_math = String::new();
@@ -164,25 +537,71 @@ impl Tui {
     }
 
     // Draws everything related to the current game state
-    fn draw_and_input(
-        &mut self,
-        game_state: GameState,
-        nerds: &Option<Nerds>,
-        current_nerd: usize,
-        equation: &str,
-    ) {
+    fn draw_and_input(&mut self, game_state: GameState, duel: Option<&Duel>, equation: &str) {
+        let nerds = duel.map(|duel| &duel.nerds);
+        let current_nerd = duel.map_or(0, |duel| duel.current_nerd);
+        self.typing = matches!(game_state, GameState::Creator);
+        self.choices.clear();
         match game_state {
             GameState::Intro => self.draw_intro(),
             GameState::MainMenu => {
-                self.draw_menu(current_nerd);
-                self.input_menu();
+                self.draw_main_menu();
+                self.input_menu_list();
+            }
+            GameState::NerdSelect(mode) => {
+                self.draw_menu(mode, current_nerd);
+                self.input_menu(mode);
             }
+            GameState::Dialogue(_) => self.draw_dialogue(nerds),
             GameState::InGame(state) => {
-                self.draw_game(state, nerds, current_nerd, equation);
-                self.input_game(state);
+                self.draw_game(state, duel, equation);
+                if !self.ais[current_nerd] {
+                    self.input_game(state);
+                }
             }
             GameState::GameEnd => {
-                self.draw_game(InGameState::Choosing, nerds, current_nerd, equation)
+                self.draw_game(InGameState::Choosing, duel, equation);
+                self.draw_centered_message(CONTINUE_TEXT, -self.height / 2 + 1, Color::Reset);
+            }
+            GameState::Practice(PracticeState::Setup) => {
+                self.draw_practice_setup();
+                self.input_practice_setup();
+            }
+            GameState::Practice(PracticeState::Solving) => {
+                self.draw_math(equation, false);
+                self.draw_info();
+                self.math_input();
+            }
+            GameState::Practice(PracticeState::Summary) => {
+                self.draw_info();
+                self.draw_centered_message(CONTINUE_TEXT, 4, Color::Reset);
+            }
+            GameState::Paused => {
+                if let Some(duel) = duel {
+                    self.draw_action_messages(duel);
+                    self.draw_stats(duel);
+                    self.draw_nerds(&duel.nerds, duel.current_nerd);
+                    self.draw_taunts(duel);
+                }
+                self.draw_pause_menu();
+                self.input_menu_list();
+            }
+            GameState::Options => {
+                self.draw_logo();
+                self.draw_centered_message(OPTIONS_TITLE, -4, MENU_COLOR);
+                self.draw_menu_list(-2);
+                self.draw_centered_message(OPTIONS_TEXT, self.menu.len() as i32, Color::Reset);
+                self.draw_centered_message(BACK_TEXT, self.menu.len() as i32 + 1, Color::Reset);
+                self.input_menu_list();
+            }
+            GameState::Creator => {
+                self.draw_creator();
+                self.input_menu_list();
+            }
+            GameState::Stats => {
+                self.draw_info();
+                let pos = self.info.len() as i32;
+                self.draw_centered_message(CONTINUE_TEXT, pos, Color::Reset);
             }
         }
     }
@@ -206,27 +625,197 @@ impl Tui {
     }
 
     // Draws the main menu
-    fn draw_menu(&mut self, current_nerd: usize) {
+    fn draw_main_menu(&mut self) {
+        self.draw_logo();
+        self.draw_centered_message(QUIT_TEXT, -4, Color::Reset);
+        self.draw_menu_list(-2);
+    }
+
+    // Draws the pause menu in a box over the game
+    fn draw_pause_menu(&mut self) {
+        let height = self.menu.len() as i32 + 4;
+        let (x, y) = (
+            self.width / 2 - PAUSE_WIDTH / 2,
+            self.height / 2 - height / 2,
+        );
+        self.engine
+            .fill_rect(x, y, x + PAUSE_WIDTH, y + height, pixel::pxl(' '));
+        self.engine
+            .rect(x, y, x + PAUSE_WIDTH, y + height, pixel::pxl('#'));
+        self.draw_centered_message(PAUSE_TITLE, -height / 2 + 1, MENU_COLOR);
+        self.draw_menu_list(-height / 2 + 3);
+    }
+
+    // Draws the items of the current menu starting at a position
+    fn draw_menu_list(&mut self, pos: i32) {
+        for i in 0..self.menu.len() {
+            let color = if i == self.current_menu_selection {
+                SELECT_COLOR
+            } else {
+                MENU_COLOR
+            };
+            let item = self.menu[i].clone();
+            self.draw_centered_message(&item, i as i32 + pos, color);
+        }
+    }
+
+    // Manages input in the current menu
+    fn input_menu_list(&mut self) {
+        let max = self.menu.len().saturating_sub(1);
+        if self.keys.is_pressed(UP_KEY) {
+            Self::change_selected(&mut self.current_menu_selection, max, -1);
+        } else if self.keys.is_pressed(DOWN_KEY) {
+            Self::change_selected(&mut self.current_menu_selection, max, 1);
+        }
+    }
+
+    // Draws the nerd selection menu
+    fn draw_menu(&mut self, mode: GameMode, current_nerd: usize) {
         self.draw_logo();
 
         self.draw_centered_message(QUIT_TEXT, -4, Color::Reset);
         self.draw_centered_message(START_TEXT, 1, Color::Reset);
+        self.draw_centered_message(BACK_TEXT, 2, Color::Reset);
+        self.draw_centered_message(RANDOM_TEXT, 3, Color::Reset);
 
-        let first_text = SELECT_TEXTS[0].to_string() + NERDS[self.nerd_selects[0]].name;
-        self.draw_centered_message(
-            &first_text,
-            -2,
-            Self::selection_color(self.current_nerd_selection == 0),
-        );
-        let second_text = SELECT_TEXTS[1].to_string() + NERDS[self.nerd_selects[1]].name;
-        self.draw_centered_message(
-            &second_text,
-            -1,
-            Self::selection_color(self.current_nerd_selection == 1),
-        );
+        let nerds = [
+            self.roster[self.nerd_selects[0]],
+            self.roster[self.nerd_selects[1]],
+        ];
+        if mode == GameMode::Campaign {
+            let text = CAMPAIGN_SELECT_TEXT.to_string() + nerds[0].name;
+            self.draw_centered_message(&text, -2, SELECT_COLOR);
+            self.draw_nerd(&nerds[0], 0, true);
+            self.draw_nerd_info(&nerds[0], None, 1);
+            return;
+        }
+
+        if let Some(warning) = self.mirror_warning {
+            self.draw_centered_message(warning, 0, WARNING_COLOR);
+        } else if let SelectPhase::Ban(player) = self.select_phase {
+            self.draw_centered_message(BAN_TEXTS[player], 0, WARNING_COLOR);
+        } else if let SelectPhase::Pick(player) = self.select_phase {
+            let text = if self.selection_mode == SelectionMode::Blind {
+                BLIND_PICK_TEXTS[player]
+            } else {
+                PICK_TEXTS[player]
+            };
+            self.draw_centered_message(text, 0, SELECT_COLOR);
+        }
+        if !self.bans.is_empty() {
+            let banned: Vec<&str> = self.bans.iter().map(|&ban| self.roster[ban].name).collect();
+            let text = BANNED_TEXT.to_string() + &banned.join(", ");
+            self.draw_centered_message(&text, -3, WARNING_COLOR);
+        }
+
+        let shown = [self.nerd_shown(0), self.nerd_shown(1)];
+        let positions = [1, self.width - INFO_WIDTH as i32 - 1];
+        for i in 0..2 {
+            let other = shown[1 - i].then_some(&nerds[1 - i]);
+            let name = if shown[i] { nerds[i].name } else { HIDDEN_TEXT };
+            let text = SELECT_TEXTS[i].to_string() + name;
+            self.draw_centered_message(
+                &text,
+                i as i32 - 2,
+                Self::selection_color(self.current_nerd_selection == i),
+            );
+            if shown[i] {
+                self.draw_nerd_info(&nerds[i], other, positions[i]);
+                self.draw_nerd(&nerds[i], i as i32 * 40 - 20, current_nerd == i);
+            }
+        }
+    }
+
+    // Returns whether a player's nerd can be seen by everyone
+    fn nerd_shown(&self, player: usize) -> bool {
+        self.selection_mode != SelectionMode::Blind
+            || self.select_phase == SelectPhase::Pick(player)
+    }
+
+    // Draws the stats, actions and bio of a nerd, colored by how they compare to another nerd
+    fn draw_nerd_info(&mut self, nerd: &Nerd, other: Option<&Nerd>, x: i32) {
+        let mut lines = vec![(
+            format!("{} ({} nerd)", nerd.name, nerd.subject.name()),
+            SELECT_COLOR,
+        )];
+        for line in Self::wrap(nerd.bio, INFO_WIDTH)
+            .into_iter()
+            .take(MAX_BIO_LINES)
+        {
+            lines.push((line, Color::Reset));
+        }
+        lines.push((
+            format!("Health: {}", nerd.health),
+            Self::compare_color(nerd.health, other.map(|other| other.health)),
+        ));
+        lines.push((
+            format!("Speed: {}", nerd.speed),
+            Self::compare_color(nerd.speed, other.map(|other| other.speed)),
+        ));
+        lines.push((
+            format!("Base multiplier: {}", nerd.multiplier),
+            Self::compare_color(nerd.multiplier, other.map(|other| other.multiplier)),
+        ));
+        lines.push((
+            format!(
+                "Critical hits: {}% chance, x{}",
+                nerd.critical_chance, nerd.critical_multiplier
+            ),
+            Self::compare_color(
+                nerd.critical_chance * nerd.critical_multiplier,
+                other.map(|other| other.critical_chance * other.critical_multiplier),
+            ),
+        ));
+        for action in &nerd.actions {
+            let other_value = other.and_then(|other| {
+                other
+                    .actions
+                    .iter()
+                    .find(|other_action| other_action.action_type() == action.action_type())
+                    .map(|other_action| other_action.value())
+            });
+            lines.push((
+                format!(
+                    " {}: {} {} ({})",
+                    action.base_name(),
+                    action.action_type().name(),
+                    action.value(),
+                    action.subject().name()
+                ),
+                Self::compare_color(action.value(), other_value),
+            ));
+        }
 
-        self.draw_nerd(&NERDS[self.nerd_selects[0]], -20, current_nerd == 0);
-        self.draw_nerd(&NERDS[self.nerd_selects[1]], 20, current_nerd == 1);
+        for (i, (line, color)) in lines.iter().enumerate() {
+            self.engine
+                .print_fbg(x, i as i32, line, *color, Color::Reset);
+        }
+    }
+
+    // Returns the color of a stat depending on whether it's better than another nerd's
+    fn compare_color(value: i32, other: Option<i32>) -> Color {
+        match other {
+            Some(other) if value > other => BETTER_COLOR,
+            Some(other) if value < other => WORSE_COLOR,
+            _ => Color::Reset,
+        }
+    }
+
+    // Splits text into lines no longer than a width
+    fn wrap(text: &str, width: usize) -> Vec<String> {
+        let mut lines = vec![String::new()];
+        for word in text.split_whitespace() {
+            let line = lines.last_mut().expect("there is always a line");
+            if !line.is_empty() && line.len() + word.len() + 1 > width {
+                lines.push(word.to_string());
+            } else {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+        }
+        lines
     }
 
     // Draws the logo in the main menu
@@ -248,27 +837,67 @@ impl Tui {
 
     // Draws a nerd at position with suitable color
     fn draw_nerd(&mut self, nerd: &Nerd, pos: i32, current_nerd: bool) {
-        let mut lines = nerd.sprite.lines();
-        let len = lines.next().unwrap_or(nerd.sprite).len();
+        let sprite = self.sprite_position(nerd, pos);
         self.engine.print_fbg(
-            self.width / 2 - len as i32 / 2 + pos,
-            self.height - MAX_ACTION_MESSAGES as i32 - lines.count() as i32 - 4,
+            sprite.x,
+            sprite.y,
             nerd.sprite,
             Self::nerd_color(current_nerd),
             Color::Reset,
         );
     }
 
-    // Manages input in the main menu
-    fn input_menu(&mut self) {
+    // Returns where the top left of a nerd's sprite is drawn
+    fn sprite_position(&self, nerd: &Nerd, pos: i32) -> Point {
+        let mut lines = nerd.sprite.lines();
+        let len = lines.next().unwrap_or(nerd.sprite).len();
+        Point::new(
+            self.width / 2 - len as i32 / 2 + pos,
+            self.height - MAX_ACTION_MESSAGES as i32 - lines.count() as i32 - 4,
+        )
+    }
+
+    // Draws a speech bubble above a nerd's sprite (kept left of the action list)
+    fn draw_bubble(&mut self, nerd: &Nerd, pos: i32, text: &str) {
+        let lines = Self::wrap(text, BUBBLE_WIDTH);
+        let width = lines.iter().map(String::len).max().unwrap_or(0);
+        let sprite = self.sprite_position(nerd, pos);
+        let x = sprite
+            .x
+            .min(self.width - ACTION_LIST_WIDTH as i32 - width as i32 - 7)
+            .max(0);
+        let top = sprite.y - lines.len() as i32 - 3;
+        let border = format!("+{}+", HORIZONTAL_DIVIDER.repeat(width + 2));
+        self.engine
+            .print_fbg(x, top, &border, BUBBLE_COLOR, Color::Reset);
+        for (i, line) in lines.iter().enumerate() {
+            self.engine.print_fbg(
+                x,
+                top + 1 + i as i32,
+                &format!("| {line:width$} |"),
+                BUBBLE_COLOR,
+                Color::Reset,
+            );
+        }
+        self.engine
+            .print_fbg(x, sprite.y - 2, &border, BUBBLE_COLOR, Color::Reset);
+        self.engine
+            .print_fbg(sprite.x + 1, sprite.y - 1, "\\", BUBBLE_COLOR, Color::Reset);
+    }
+
+    // Manages input in the nerd selection menu
+    fn input_menu(&mut self, mode: GameMode) {
+        if mode == GameMode::Campaign {
+            self.current_nerd_selection = 0;
+        }
         self.input_secret();
-        self.input_nerd_select();
+        self.input_nerd_select(mode == GameMode::Versus);
     }
 
-    // Deals with the Konami Code and the secret nerd
+    // Deals with the Konami Code
     fn input_secret(&mut self) {
         if self.secret_index == SECRET_SEQUENCE.len() {
-            self.nerd_selects[self.current_nerd_selection] = NERDS.len() - 1;
+            self.secret_entered = true;
             self.secret_index = 0;
         }
         if self.secret_key((' '..='~').map(KeyCode::Char)) {
@@ -300,7 +929,7 @@ impl Tui {
     // Checks whether a keycode is part of the secret, and does stuff with it
     fn secret_key<T: IntoIterator<Item = KeyCode>>(&mut self, keys: T) -> bool {
         for key in keys {
-            let pressed = self.engine.is_key_pressed(key);
+            let pressed = self.keys.is_pressed(key);
             if pressed && key == SECRET_SEQUENCE[self.secret_index] {
                 self.secret_index += 1;
                 return true;
@@ -312,25 +941,54 @@ impl Tui {
         false
     }
 
-    // What nerd does the player select
-    fn input_nerd_select(&mut self) {
-        let len = NERDS.len() - 1;
-        let len = if self.nerd_selects[self.current_nerd_selection] == len {
-            len
+    // What nerd does the player select (and which player is selecting if there are two)
+    fn input_nerd_select(&mut self, two_players: bool) {
+        let player = self.current_nerd_selection;
+        let available = self.available_nerds(player);
+        let len = available.len();
+        let pos = available
+            .iter()
+            .position(|&nerd| nerd == self.nerd_selects[player])
+            .unwrap_or(0);
+        let switching = two_players && self.select_phase == SelectPhase::Both;
+
+        if self.keys.is_pressed(UP_KEY) && switching {
+            Self::change_selected(&mut self.current_nerd_selection, 1, 1);
+        } else if self.keys.is_pressed(DOWN_KEY) && switching {
+            Self::change_selected(&mut self.current_nerd_selection, 1, -1);
+        } else if self.keys.is_pressed(LEFT_KEY) {
+            self.nerd_selects[player] = available[(pos + len - 1) % len];
+        } else if self.keys.is_pressed(RIGHT_KEY) {
+            self.nerd_selects[player] = available[(pos + 1) % len];
+        } else if self.keys.is_pressed(RANDOM_KEY) {
+            self.nerd_selects[player] = available[fastrand::usize(0..len)];
         } else {
-            len - 1
-        };
-        let select = &mut self.nerd_selects[self.current_nerd_selection];
-        let selection = &mut self.current_nerd_selection;
+            return;
+        }
+        self.mirror_warning = None;
+    }
+
+    // Returns the unlocked nerds a player can currently select
+    fn available_nerds(&self, player: usize) -> Vec<usize> {
+        let taken = (!self.allow_mirror
+            && self.selection_mode == SelectionMode::Draft
+            && self.select_phase == SelectPhase::Pick(1)
+            && player == 1)
+            .then_some(self.nerd_selects[0]);
+        self.selectable
+            .iter()
+            .copied()
+            .filter(|nerd| !self.bans.contains(nerd) && Some(*nerd) != taken)
+            .collect()
+    }
 
-        if self.engine.is_key_pressed(UP_KEY) {
-            Self::change_selected(selection, 1, 1);
-        } else if self.engine.is_key_pressed(DOWN_KEY) {
-            Self::change_selected(selection, 1, -1);
-        } else if self.engine.is_key_pressed(LEFT_KEY) {
-            Self::change_selected(select, len, -1);
-        } else if self.engine.is_key_pressed(RIGHT_KEY) {
-            Self::change_selected(select, len, 1);
+    // Moves the players' selections off of nerds they can't select anymore
+    fn fix_nerd_selects(&mut self) {
+        for player in 0..2 {
+            let available = self.available_nerds(player);
+            if !available.contains(&self.nerd_selects[player]) {
+                self.nerd_selects[player] = available[0];
+            }
         }
     }
 
@@ -345,49 +1003,173 @@ impl Tui {
         }
     }
 
+    // Draws the current line of dialogue with the nerds it's between
+    fn draw_dialogue(&mut self, nerds: Option<&Nerds>) {
+        if let Some(nerds) = nerds {
+            self.draw_nerds(nerds, 0);
+        }
+        if let Some(line) = self.dialogue.get(self.dialogue_line) {
+            let line = line.clone();
+            self.draw_centered_message(&line, -self.height / 2 + 3, DIALOGUE_COLOR);
+        }
+        self.draw_centered_message(CONTINUE_TEXT, -self.height / 2 + 5, Color::Reset);
+    }
+
+    // Draws the practice settings
+    fn draw_practice_setup(&mut self) {
+        self.draw_logo();
+        self.draw_centered_message(PRACTICE_TITLE, -4, MENU_COLOR);
+
+        let values = [
+            practice::operation_name(OPERATIONS[self.practice_selects[0]]).to_string(),
+            DIFFICULTIES[self.practice_selects[1]].to_string(),
+            LENGTHS[self.practice_selects[2]].name(),
+        ];
+        for (i, value) in values.iter().enumerate() {
+            let text = PRACTICE_SETTING_TEXTS[i].to_string() + value;
+            self.draw_centered_message(
+                &text,
+                i as i32 - 2,
+                Self::selection_color(self.current_practice_selection == i),
+            );
+        }
+
+        self.draw_centered_message(START_TEXT, 2, Color::Reset);
+        self.draw_centered_message(BACK_TEXT, 3, Color::Reset);
+    }
+
+    // Changes the practice settings
+    fn input_practice_setup(&mut self) {
+        let lens = [OPERATIONS.len(), DIFFICULTIES.len(), LENGTHS.len()];
+        let selection = &mut self.current_practice_selection;
+        let select = &mut self.practice_selects[*selection];
+
+        if self.keys.is_pressed(UP_KEY) {
+            Self::change_selected(selection, lens.len() - 1, -1);
+        } else if self.keys.is_pressed(DOWN_KEY) {
+            Self::change_selected(selection, lens.len() - 1, 1);
+        } else if self.keys.is_pressed(LEFT_KEY) {
+            Self::change_selected(select, lens[*selection] - 1, -1);
+        } else if self.keys.is_pressed(RIGHT_KEY) {
+            Self::change_selected(select, lens[*selection] - 1, 1);
+        }
+    }
+
+    // Draws the nerd creator's items, its information and the sprite being made
+    fn draw_creator(&mut self) {
+        let top = -self.height / 2 + 1;
+        self.draw_centered_message(CREATOR_TITLE, top, MENU_COLOR);
+        self.draw_menu_list(top + 2);
+        let mut pos = top + self.menu.len() as i32 + 3;
+        for line in self
+            .info
+            .clone()
+            .iter()
+            .chain(&CREATOR_TEXTS.map(String::from))
+        {
+            self.draw_centered_message(line, pos, Color::Reset);
+            pos += 1;
+        }
+        self.draw_centered_message(BACK_TEXT, pos, Color::Reset);
+        self.engine.print_fbg(
+            PREVIEW_POSITION[0],
+            PREVIEW_POSITION[1],
+            &self.preview,
+            CURRENT_NERD_COLOR,
+            Color::Reset,
+        );
+    }
+
+    // Draws the lines of information in the middle of the screen
+    fn draw_info(&mut self) {
+        for (i, line) in self.info.clone().iter().enumerate() {
+            self.draw_centered_message(line, i as i32 - 2, Color::Reset);
+        }
+    }
+
     // Draws the game
-    fn draw_game(
-        &mut self,
-        in_game_state: InGameState,
-        nerds: &Option<Nerds>,
-        current_nerd: usize,
-        equation: &str,
-    ) {
-        if let Some(nerds) = &nerds {
+    fn draw_game(&mut self, in_game_state: InGameState, duel: Option<&Duel>, equation: &str) {
+        if let Some(duel) = duel {
+            let (nerds, current_nerd) = (&duel.nerds, duel.current_nerd);
             match in_game_state {
                 InGameState::Choosing => {
-                    self.draw_action_messages();
-                    self.draw_stats(nerds, current_nerd);
+                    self.draw_action_messages(duel);
+                    self.draw_stats(duel);
+                    self.draw_turn_order(duel);
                     self.draw_nerds(nerds, current_nerd);
-                    self.draw_action_list(nerds, current_nerd);
+                    self.draw_taunts(duel);
+                    self.draw_action_list(duel);
+                    if self.reveal_critical && duel.critical() {
+                        self.engine.print_fbg(
+                            self.width - ACTION_LIST_WIDTH as i32,
+                            self.height / 2 - 6,
+                            CRITICAL_READY_TEXT,
+                            CRITICAL_COLORS[0],
+                            Color::Reset,
+                        );
+                    }
                 }
                 InGameState::Mathing => {
-                    self.draw_action_messages();
-                    self.draw_stats(nerds, current_nerd);
+                    self.draw_action_messages(duel);
+                    self.draw_stats(duel);
+                    self.draw_turn_order(duel);
+                    self.draw_nerds(nerds, current_nerd);
+                    self.draw_taunts(duel);
+                    self.choices = duel.choices();
+                    self.draw_math(equation, duel.missing());
+                    if !self.choices.is_empty() {
+                        self.draw_choice_list();
+                    }
+                }
+                InGameState::Taunting => {
+                    self.draw_action_messages(duel);
+                    self.draw_stats(duel);
+                    self.draw_turn_order(duel);
                     self.draw_nerds(nerds, current_nerd);
-                    self.draw_math(equation);
+                    self.draw_taunts(duel);
+                    self.draw_taunt_list(&nerds[current_nerd]);
                 }
             }
         }
+        self.draw_critical_flash();
+    }
+
+    // Draws a blinking border and message while a critical hit is being flashed
+    fn draw_critical_flash(&mut self) {
+        if self.flash_frames == 0 {
+            return;
+        }
+        self.flash_frames -= 1;
+        let color = CRITICAL_COLORS[self.flash_frames / FLASH_BLINK_FRAMES % CRITICAL_COLORS.len()];
+        self.engine.rect(
+            0,
+            0,
+            self.width - 1,
+            self.height - 1,
+            pixel::pxl_fg('*', color),
+        );
+        self.draw_centered_message(CRITICAL_TEXT, -self.height / 2 + 2, color);
     }
 
     // Draws a list of messages stating the actions that have been done
-    fn draw_action_messages(&mut self) {
+    fn draw_action_messages(&mut self, duel: &Duel) {
         self.engine.print(
             0,
             self.height - MAX_ACTION_MESSAGES as i32 - 1,
             &HORIZONTAL_DIVIDER.repeat(self.width as usize),
         );
 
-        for (i, message) in self.action_messages.iter().enumerate() {
+        let messages = duel.messages();
+        let messages = &messages[messages.len().saturating_sub(MAX_ACTION_MESSAGES)..];
+        for (i, message) in messages.iter().enumerate() {
             self.engine
                 .print(1, self.height - (MAX_ACTION_MESSAGES - i) as i32, message);
         }
 
-        if let Some(message) = self.action_messages.last() {
+        if let Some(message) = messages.last() {
             self.engine.print_fbg(
                 1,
-                self.height - (MAX_ACTION_MESSAGES - self.action_messages.len() + 1) as i32,
+                self.height - (MAX_ACTION_MESSAGES - messages.len() + 1) as i32,
                 message,
                 SELECT_COLOR,
                 Color::Reset,
@@ -395,21 +1177,21 @@ impl Tui {
         }
     }
 
-    // Prints the stats of the nerds (health, multiplier)
-    fn draw_stats(&mut self, nerds: &Nerds, current_nerd: usize) {
+    // Prints the stats of the nerds (health, multiplier, difficulty level)
+    fn draw_stats(&mut self, duel: &Duel) {
         self.engine.print_fbg(
             0,
             self.height - MAX_ACTION_MESSAGES as i32 - 2,
-            &self.stats_string(&nerds[0]),
-            Self::nerd_color(0 == current_nerd),
+            &self.stats_string(&duel.nerds[0], &duel.difficulties[0]),
+            Self::nerd_color(0 == duel.current_nerd),
             Color::Reset,
         );
-        let stats = self.stats_string(&nerds[1]);
+        let stats = self.stats_string(&duel.nerds[1], &duel.difficulties[1]);
         self.engine.print_fbg(
             self.width - stats.len() as i32,
             self.height - MAX_ACTION_MESSAGES as i32 - 2,
             &stats,
-            Self::nerd_color(1 == current_nerd),
+            Self::nerd_color(1 == duel.current_nerd),
             Color::Reset,
         );
         self.engine.print(
@@ -419,11 +1201,38 @@ impl Tui {
         );
     }
 
+    // Draws whose turn it is and whose turn is next (with their speeds)
+    fn draw_turn_order(&mut self, duel: &Duel) {
+        let (current, other) = (
+            &duel.nerds[duel.current_nerd],
+            &duel.nerds[duel.other_nerd()],
+        );
+        let mut text = format!(
+            "Turn order: {} (speed {}) now, then {} (speed {})",
+            current.name, current.speed, other.name, other.speed
+        );
+        if duel.extra_turns && current.speed > other.speed {
+            text += &format!(" unless {} goes again", current.name);
+        }
+        self.engine.print_fbg(
+            1,
+            TURN_ORDER_ROW,
+            &text,
+            Self::nerd_color(true),
+            Color::Reset,
+        );
+    }
+
     // Returns the string used for printing the nerd's stats
-    fn stats_string(&self, nerd: &Nerd) -> String {
+    fn stats_string(&self, nerd: &Nerd, difficulty: &Difficulty) -> String {
         format!(
-            " {}: Health = {}, Multiplier = {} ",
-            nerd.name, nerd.health, nerd.multiplier,
+            " {}: Health = {}/{}, Multiplier = {}, Level = {} ({}) ",
+            nerd.name,
+            nerd.health,
+            nerd.max_health,
+            nerd.multiplier,
+            difficulty.level(),
+            difficulty.name(),
         )
     }
 
@@ -438,38 +1247,148 @@ impl Tui {
 
     // Draws the nerds of the game with suitable colors
     fn draw_nerds(&mut self, nerds: &Nerds, current_nerd: usize) {
-        self.draw_nerd(&nerds[0], -50, current_nerd == 0);
-        self.draw_nerd(&nerds[1], 10, current_nerd == 1);
+        for (i, nerd) in nerds.iter().enumerate() {
+            self.draw_nerd(nerd, NERD_POSITIONS[i], current_nerd == i);
+        }
     }
 
-    // Draws the list of actions that the current nerd can use
-    fn draw_action_list(&mut self, nerds: &Nerds, current_nerd: usize) {
+    // Draws speech bubbles above the nerds who taunted this turn
+    fn draw_taunts(&mut self, duel: &Duel) {
+        for (i, nerd) in duel.nerds.iter().enumerate() {
+            if let Some(taunt) = duel.taunt_said(i) {
+                self.draw_bubble(nerd, NERD_POSITIONS[i], taunt);
+            }
+        }
+    }
+
+    // Draws the divider between the action list and the rest of the game
+    fn draw_action_divider(&mut self) {
         self.engine.print(
             (self.width - ACTION_LIST_WIDTH as i32) - 2,
             0,
             &VERTICAL_DIVIDER.repeat(self.height as usize - MAX_ACTION_MESSAGES - 3),
         );
+    }
+
+    // Draws the list of actions that the current nerd can use
+    fn draw_action_list(&mut self, duel: &Duel) {
+        self.draw_action_divider();
+
+        let (nerds, current_nerd) = (&duel.nerds, duel.current_nerd);
+        let (nerd, other) = (&nerds[current_nerd], &nerds[1 - current_nerd]);
+        let actions = &nerd.actions;
+        for (i, action) in actions.iter().enumerate() {
+            let mut name = action.name();
+            let effectiveness = nerd.effectiveness(i, other);
+            if effectiveness > 1 {
+                name += &format!(" x{effectiveness}!");
+            }
+            let locked =
+                !self.ais[current_nerd] && self.locked_actions.contains(&action.base_name());
+            self.draw_action(i as i32, &name, i == self.current_action_selection, locked);
+        }
+        if self.ais[current_nerd] {
+            return;
+        }
+        let hint = if self.hint.is_empty() {
+            vec![HINT_TEXT.to_string()]
+        } else {
+            self.hint.clone()
+        };
+        for (i, line) in hint.iter().enumerate() {
+            self.engine.print_fbg(
+                self.width - ACTION_LIST_WIDTH as i32,
+                (actions.len() + 1 + i) as i32 + self.height / 2 - 4,
+                line,
+                HINT_COLOR,
+                Color::Reset,
+            );
+        }
+        if duel.can_taunt(current_nerd) {
+            self.engine.print(
+                self.width - ACTION_LIST_WIDTH as i32,
+                (actions.len() + 2 + hint.len()) as i32 + self.height / 2 - 4,
+                TAUNT_TEXT,
+            );
+        }
+    }
 
-        for (i, action) in nerds[current_nerd].actions.iter().enumerate() {
-            self.draw_action(i as i32, &action.name(), i == self.current_action_selection);
+    // Draws the list of taunts that the current nerd can say
+    fn draw_taunt_list(&mut self, nerd: &Nerd) {
+        self.draw_action_divider();
+        let x = self.width - ACTION_LIST_WIDTH as i32;
+        let mut y = self.height / 2 - 6;
+        self.engine
+            .print_fbg(x, y, TAUNTS_TITLE, MENU_COLOR, Color::Reset);
+        y += 2;
+        for (i, taunt) in nerd.taunts.iter().enumerate() {
+            for line in Self::wrap(taunt, ACTION_LIST_WIDTH - 1) {
+                self.engine.print_fbg(
+                    x,
+                    y,
+                    &line,
+                    Self::selection_color(i == self.current_taunt_selection),
+                    Color::Reset,
+                );
+                y += 1;
+            }
         }
     }
 
-    // Draws an action in the action list
-    fn draw_action(&mut self, pos: i32, name: &str, selected: bool) {
+    // Draws the answers to choose from in multiple choice
+    fn draw_choice_list(&mut self) {
+        self.draw_action_divider();
+        let x = self.width - ACTION_LIST_WIDTH as i32;
+        let y = self.height / 2 - 6;
+        self.engine
+            .print_fbg(x, y, CHOICES_TITLE, MENU_COLOR, Color::Reset);
+        for (i, choice) in self.choices.clone().iter().enumerate() {
+            self.engine.print_fbg(
+                x,
+                y + 2 + i as i32,
+                &format!("{}. {choice}", i + 1),
+                Self::selection_color(i == self.current_choice_selection),
+                Color::Reset,
+            );
+        }
+        self.engine.print_fbg(
+            x,
+            y + 3 + self.choices.len() as i32,
+            CHOICES_TEXT,
+            HINT_COLOR,
+            Color::Reset,
+        );
+    }
+
+    // Draws an action in the action list (greyed out if it's locked)
+    fn draw_action(&mut self, pos: i32, name: &str, selected: bool, locked: bool) {
         self.engine.print_fbg(
             self.width - ACTION_LIST_WIDTH as i32,
             pos + self.height / 2 - 4,
             name,
-            Self::selection_color(selected),
+            if locked && !selected {
+                LOCKED_ACTION_COLOR
+            } else {
+                Self::selection_color(selected)
+            },
             Color::Reset,
         );
     }
 
-    // Draws the math input bar
-    fn draw_math(&mut self, equation: &str) {
-        self.engine
-            .print(0, 0, &format!("{} = {}", equation, self.inputted_math));
+    // Draws the math input bar (with a question mark if answers are multiple choice), where the missing
+    // number is typed instead if the equation has one
+    fn draw_math(&mut self, equation: &str, missing: bool) {
+        let answer = if self.choices.is_empty() {
+            &self.inputted_math
+        } else {
+            "?"
+        };
+        let text = if missing {
+            format!("{equation}, so ? = {answer}")
+        } else {
+            format!("{equation} = {answer}")
+        };
+        self.engine.print(0, 0, &text);
         self.engine
             .print(0, 1, &HORIZONTAL_DIVIDER.repeat(self.width as usize));
     }
@@ -479,30 +1398,67 @@ impl Tui {
         match state {
             InGameState::Choosing => self.action_list_input(),
             InGameState::Mathing => self.math_input(),
+            InGameState::Taunting => self.taunt_list_input(),
+        }
+    }
+
+    // Process input for switching the current taunt
+    fn taunt_list_input(&mut self) {
+        if self.keys.is_pressed(UP_KEY) {
+            Self::change_selected(&mut self.current_taunt_selection, 3, -1);
+        } else if self.keys.is_pressed(DOWN_KEY) {
+            Self::change_selected(&mut self.current_taunt_selection, 3, 1);
         }
     }
 
     // Process input for switching the current action
     fn action_list_input(&mut self) {
-        if self.engine.is_key_pressed(UP_KEY) {
+        if self.keys.is_pressed(UP_KEY) {
             Self::change_selected(&mut self.current_action_selection, 3, -1);
-        } else if self.engine.is_key_pressed(DOWN_KEY) {
+        } else if self.keys.is_pressed(DOWN_KEY) {
             Self::change_selected(&mut self.current_action_selection, 3, 1);
         }
     }
 
     // Processes input for solving math equations
     fn math_input(&mut self) {
-        if self.engine.is_key_pressed(KeyCode::Char('-')) && self.inputted_math.is_empty() {
+        if !self.choices.is_empty() {
+            self.choice_input();
+            return;
+        }
+        if self.keys.is_pressed(KeyCode::Char('-')) && self.inputted_math.is_empty() {
             self.inputted_math.push('-')
         }
         for num in '0'..='9' {
-            if self.engine.is_key_pressed(KeyCode::Char(num)) {
+            if self.keys.is_pressed(KeyCode::Char(num)) {
                 self.inputted_math.push(num);
             }
         }
-        if self.engine.is_key_pressed(KeyCode::Backspace) {
+        if self.keys.is_pressed(KeyCode::Backspace) {
             self.inputted_math.pop();
         }
     }
+
+    // Processes input for choosing an answer in multiple choice
+    fn choice_input(&mut self) {
+        let max = self.choices.len() - 1;
+        if self.keys.is_pressed(UP_KEY) || self.keys.is_pressed(LEFT_KEY) {
+            Self::change_selected(&mut self.current_choice_selection, max, -1);
+        } else if self.keys.is_pressed(DOWN_KEY) || self.keys.is_pressed(RIGHT_KEY) {
+            Self::change_selected(&mut self.current_choice_selection, max, 1);
+        }
+        for (i, num) in ('1'..='9').take(self.choices.len()).enumerate() {
+            if self.keys.is_pressed(KeyCode::Char(num)) {
+                self.choice_pressed = Some(i);
+            }
+        }
+    }
+}
+
+// Which players are banning or picking while selecting nerds
+#[derive(Copy, Clone, PartialEq)]
+enum SelectPhase {
+    Both,
+    Ban(usize),
+    Pick(usize),
 }
@@ -0,0 +1,184 @@
+use crate::storage::Save;
+
+// Name of the file the unlocks are saved in
+const SAVE_NAME: &str = "unlocks";
+
+// Everything that starts locked, and what unlocks it (any one of its conditions is enough);
+// locked actions are only kept from players, so the computer can still use them
+const UNLOCKS: [Unlock; 5] = [
+    Unlock::new(Unlockable::Nerd("Yamin"), Condition::Sequence),
+    Unlock::new(Unlockable::Nerd("Yamin"), Condition::CampaignComplete),
+    Unlock::new(
+        Unlockable::Mode("Blind pick"),
+        Condition::Achievement(FLAWLESS),
+    ),
+    Unlock::new(Unlockable::Mode("Draft and ban"), Condition::Wins(3)),
+    Unlock::new(
+        Unlockable::Action("Watch Dhar Mann Video"),
+        Condition::Wins(1),
+    ),
+];
+
+// Achievements that can be earned
+pub const FLAWLESS: &str = "Flawless";
+
+// Something that is locked until a condition is met
+#[derive(Copy, Clone)]
+struct Unlock {
+    unlockable: Unlockable,
+    condition: Condition,
+}
+
+impl Unlock {
+    // Creates a new unlock
+    const fn new(unlockable: Unlockable, condition: Condition) -> Self {
+        Self {
+            unlockable,
+            condition,
+        }
+    }
+}
+
+// Things that can be locked (by name)
+#[derive(Copy, Clone, PartialEq)]
+pub enum Unlockable {
+    Nerd(&'static str),
+    Action(&'static str),
+    Mode(&'static str),
+}
+
+impl Unlockable {
+    // Returns the text shown when it's unlocked
+    pub fn name(&self) -> String {
+        match self {
+            Self::Nerd(name) => format!("{name} (nerd)"),
+            Self::Action(name) => format!("{name} (action)"),
+            Self::Mode(name) => format!("{name} (mode)"),
+        }
+    }
+
+    // Returns the text written for a save file
+    fn save_text(&self) -> String {
+        match self {
+            Self::Nerd(name) => format!("nerd {name}"),
+            Self::Action(name) => format!("action {name}"),
+            Self::Mode(name) => format!("mode {name}"),
+        }
+    }
+}
+
+// What has to be done to unlock something
+#[derive(Copy, Clone)]
+enum Condition {
+    Sequence,
+    CampaignComplete,
+    Achievement(&'static str),
+    Wins(usize),
+}
+
+// What the players have unlocked and done so far
+pub struct Unlocks {
+    unlocked: Vec<String>,
+    achievements: Vec<String>,
+    wins: usize,
+    campaign_complete: bool,
+}
+
+impl Unlocks {
+    // Loads the saved unlocks, otherwise nothing is unlocked
+    pub fn load() -> Self {
+        let save = Save::load(SAVE_NAME).unwrap_or_default();
+        Self {
+            unlocked: save.get_all("unlocked").map(String::from).collect(),
+            achievements: save.get_all("achievement").map(String::from).collect(),
+            wins: save.get_parsed("wins").unwrap_or(0),
+            campaign_complete: save.get_parsed("campaign_complete").unwrap_or(false),
+        }
+    }
+
+    // Saves the unlocks
+    fn save(&self) {
+        let mut save = Save::new();
+        for unlocked in &self.unlocked {
+            save.set("unlocked", unlocked);
+        }
+        for achievement in &self.achievements {
+            save.set("achievement", achievement);
+        }
+        save.set("wins", self.wins);
+        save.set("campaign_complete", self.campaign_complete);
+        let _ = save.write(SAVE_NAME);
+    }
+
+    // Returns whether something can be used (things that were never locked always can)
+    pub fn is_unlocked(&self, unlockable: Unlockable) -> bool {
+        Self::is_starting(unlockable) || self.unlocked.contains(&unlockable.save_text())
+    }
+
+    // Returns the names of the actions players can't use yet
+    pub fn locked_actions(&self) -> Vec<&'static str> {
+        UNLOCKS
+            .iter()
+            .filter_map(|unlock| match unlock.unlockable {
+                Unlockable::Action(name) if !self.is_unlocked(unlock.unlockable) => Some(name),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Returns whether something is always available without having to be unlocked
+    pub fn is_starting(unlockable: Unlockable) -> bool {
+        !UNLOCKS.iter().any(|unlock| unlock.unlockable == unlockable)
+    }
+
+    // Unlocks what the secret sequence unlocks; returns everything it unlocks (even if already unlocked)
+    pub fn enter_sequence(&mut self) -> Vec<Unlockable> {
+        self.check(true);
+        UNLOCKS
+            .iter()
+            .filter(|unlock| matches!(unlock.condition, Condition::Sequence))
+            .map(|unlock| unlock.unlockable)
+            .collect()
+    }
+
+    // Records the campaign being completed; returns what was newly unlocked
+    pub fn complete_campaign(&mut self) -> Vec<Unlockable> {
+        self.campaign_complete = true;
+        self.check(false)
+    }
+
+    // Records a player winning a duel; returns what was newly unlocked
+    pub fn add_win(&mut self) -> Vec<Unlockable> {
+        self.wins += 1;
+        self.check(false)
+    }
+
+    // Records an achievement being earned; returns what was newly unlocked
+    pub fn achieve(&mut self, achievement: &str) -> Vec<Unlockable> {
+        if !self.achievements.iter().any(|earned| earned == achievement) {
+            self.achievements.push(achievement.to_string());
+        }
+        self.check(false)
+    }
+
+    // Unlocks everything whose condition is met, then saves; returns what was newly unlocked
+    fn check(&mut self, sequence: bool) -> Vec<Unlockable> {
+        let mut unlocked = Vec::new();
+        for unlock in UNLOCKS {
+            let met = match unlock.condition {
+                Condition::Sequence => sequence,
+                Condition::CampaignComplete => self.campaign_complete,
+                Condition::Achievement(achievement) => {
+                    self.achievements.iter().any(|earned| earned == achievement)
+                }
+                Condition::Wins(wins) => self.wins >= wins,
+            };
+            if met && !self.is_unlocked(unlock.unlockable) {
+                self.unlocked.push(unlock.unlockable.save_text());
+                unlocked.push(unlock.unlockable);
+            }
+        }
+        self.save();
+        unlocked
+    }
+}